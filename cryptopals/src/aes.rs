@@ -0,0 +1,372 @@
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+
+/// AES always operates on 128 bit blocks regardless of key size
+///
+pub const BLOCK_SIZE: usize = 16;
+
+static SBOX: [u8; 256] = init_sbox();
+static INV_SBOX: [u8; 256] = init_inv_sbox();
+
+//Round constants used by the key schedule (x^(i-1) in GF(2^8))
+static RCON: [u8; 11] = [
+    0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36,
+];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AesError {
+    InvalidKeyLength,
+}
+
+impl Error for AesError {
+    fn description(&self) -> &str {
+        match *self {
+            AesError::InvalidKeyLength => "AES key must be 16, 24 or 32 bytes",
+        }
+    }
+}
+
+impl fmt::Display for AesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AesError::InvalidKeyLength => write!(f, "AES key must be 16, 24 or 32 bytes"),
+        }
+    }
+}
+
+/// AES block cipher (FIPS-197) for 128, 192 and 256 bit keys.
+/// Encrypts and decrypts single blocks, modes of operation are built on top.
+///
+#[derive(Clone)]
+pub struct Aes {
+    round_keys: Vec<[u8; BLOCK_SIZE]>,
+}
+
+impl Aes {
+    /// Create the cipher, expanding the key into the round keys.
+    /// The key length selects AES-128, AES-192 or AES-256.
+    ///
+    pub fn new(key: &[u8]) -> std::result::Result<Aes, AesError> {
+        Ok(Aes {
+            round_keys: expand_key(key)?,
+        })
+    }
+
+    /// The round keys produced by the key schedule, one per round plus the initial whitening key
+    ///
+    pub fn round_keys(&self) -> &[[u8; BLOCK_SIZE]] {
+        &self.round_keys
+    }
+
+    /// Number of rounds (10, 12 or 14 depending on key size)
+    ///
+    pub fn num_rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    /// Encrypt a single block in place
+    ///
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+        let rounds = self.num_rounds();
+        add_round_key(block, &self.round_keys[0]);
+
+        for round_key in self.round_keys[1..rounds].iter() {
+            sub_bytes(block);
+            shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, round_key);
+        }
+
+        sub_bytes(block);
+        shift_rows(block);
+        add_round_key(block, &self.round_keys[rounds]);
+    }
+
+    /// Decrypt a single block in place
+    ///
+    pub fn decrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+        let rounds = self.num_rounds();
+        add_round_key(block, &self.round_keys[rounds]);
+
+        for round_key in self.round_keys[1..rounds].iter().rev() {
+            inv_shift_rows(block);
+            inv_sub_bytes(block);
+            add_round_key(block, round_key);
+            inv_mix_columns(block);
+        }
+
+        inv_shift_rows(block);
+        inv_sub_bytes(block);
+        add_round_key(block, &self.round_keys[0]);
+    }
+}
+
+/// The AES key schedule. Expands a 16, 24 or 32 byte key into the per round keys.
+///
+pub fn expand_key(key: &[u8]) -> std::result::Result<Vec<[u8; BLOCK_SIZE]>, AesError> {
+    let nk = match key.len() {
+        16 | 24 | 32 => key.len() / 4,
+        _ => return Err(AesError::InvalidKeyLength),
+    };
+    let rounds = nk + 6;
+    let num_words = 4 * (rounds + 1);
+
+    let mut words: Vec<[u8; 4]> = key
+        .chunks(4)
+        .map(|w| w.try_into().unwrap())
+        .collect::<Vec<_>>();
+
+    for i in nk..num_words {
+        let mut temp = words[i - 1];
+        if i % nk == 0 {
+            temp.rotate_left(1);
+            sub_word(&mut temp);
+            temp[0] ^= RCON[i / nk];
+        } else if nk > 6 && i % nk == 4 {
+            sub_word(&mut temp);
+        }
+
+        let prev = words[i - nk];
+        words.push([
+            prev[0] ^ temp[0],
+            prev[1] ^ temp[1],
+            prev[2] ^ temp[2],
+            prev[3] ^ temp[3],
+        ]);
+    }
+
+    Ok(words
+        .chunks(4)
+        .map(|w| {
+            let mut round_key = [0u8; BLOCK_SIZE];
+            for (i, word) in w.iter().enumerate() {
+                round_key[i * 4..i * 4 + 4].copy_from_slice(word);
+            }
+            round_key
+        })
+        .collect())
+}
+
+fn sub_word(word: &mut [u8; 4]) {
+    for b in word.iter_mut() {
+        *b = SBOX[*b as usize];
+    }
+}
+
+fn add_round_key(state: &mut [u8; BLOCK_SIZE], round_key: &[u8; BLOCK_SIZE]) {
+    for (s, k) in state.iter_mut().zip(round_key.iter()) {
+        *s ^= k;
+    }
+}
+
+fn sub_bytes(state: &mut [u8; BLOCK_SIZE]) {
+    for s in state.iter_mut() {
+        *s = SBOX[*s as usize];
+    }
+}
+
+fn inv_sub_bytes(state: &mut [u8; BLOCK_SIZE]) {
+    for s in state.iter_mut() {
+        *s = INV_SBOX[*s as usize];
+    }
+}
+
+/// The state is stored column major so row r of column c is at r + 4c.
+/// Row r is rotated left by r columns.
+///
+fn shift_rows(state: &mut [u8; BLOCK_SIZE]) {
+    let s = *state;
+    for c in 0..4 {
+        for r in 1..4 {
+            state[r + 4 * c] = s[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+fn inv_shift_rows(state: &mut [u8; BLOCK_SIZE]) {
+    let s = *state;
+    for c in 0..4 {
+        for r in 1..4 {
+            state[r + 4 * ((c + r) % 4)] = s[r + 4 * c];
+        }
+    }
+}
+
+fn mix_columns(state: &mut [u8; BLOCK_SIZE]) {
+    for col in state.chunks_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        col[0] = gf_mul(a0, 2) ^ gf_mul(a1, 3) ^ a2 ^ a3;
+        col[1] = a0 ^ gf_mul(a1, 2) ^ gf_mul(a2, 3) ^ a3;
+        col[2] = a0 ^ a1 ^ gf_mul(a2, 2) ^ gf_mul(a3, 3);
+        col[3] = gf_mul(a0, 3) ^ a1 ^ a2 ^ gf_mul(a3, 2);
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8; BLOCK_SIZE]) {
+    for col in state.chunks_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        col[0] = gf_mul(a0, 14) ^ gf_mul(a1, 11) ^ gf_mul(a2, 13) ^ gf_mul(a3, 9);
+        col[1] = gf_mul(a0, 9) ^ gf_mul(a1, 14) ^ gf_mul(a2, 11) ^ gf_mul(a3, 13);
+        col[2] = gf_mul(a0, 13) ^ gf_mul(a1, 9) ^ gf_mul(a2, 14) ^ gf_mul(a3, 11);
+        col[3] = gf_mul(a0, 11) ^ gf_mul(a1, 13) ^ gf_mul(a2, 9) ^ gf_mul(a3, 14);
+    }
+}
+
+/// Multiplication in GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1
+///
+const fn gf_mul(a: u8, b: u8) -> u8 {
+    let mut a = a;
+    let mut b = b;
+    let mut product = 0u8;
+    while b > 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Build the S-box from its definition: the multiplicative inverse in GF(2^8)
+/// followed by the affine transform
+///
+const fn init_sbox() -> [u8; 256] {
+    let mut sbox = [0u8; 256];
+    let mut x = 0;
+    while x < 256 {
+        //x^254 is the inverse of x (and maps 0 to 0)
+        let mut inv = 1u8;
+        let mut i = 0;
+        while i < 254 {
+            inv = gf_mul(inv, x as u8);
+            i += 1;
+        }
+        if x == 0 {
+            inv = 0;
+        }
+
+        sbox[x] = inv
+            ^ inv.rotate_left(1)
+            ^ inv.rotate_left(2)
+            ^ inv.rotate_left(3)
+            ^ inv.rotate_left(4)
+            ^ 0x63;
+        x += 1;
+    }
+    sbox
+}
+
+const fn init_inv_sbox() -> [u8; 256] {
+    let sbox = init_sbox();
+    let mut inv_sbox = [0u8; 256];
+    let mut x = 0;
+    while x < 256 {
+        inv_sbox[sbox[x] as usize] = x as u8;
+        x += 1;
+    }
+    inv_sbox
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::hex_converter::hex_decode;
+
+#[cfg(test)]
+fn fips197_block(key_hex: &str, expected_hex: &str) {
+    let key = hex_decode(key_hex).unwrap();
+    let plaintext: [u8; BLOCK_SIZE] = hex_decode("00112233445566778899aabbccddeeff")
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let aes = Aes::new(&key).unwrap();
+    let mut block = plaintext;
+    aes.encrypt_block(&mut block);
+    assert_eq!(block.to_vec(), hex_decode(expected_hex).unwrap());
+
+    aes.decrypt_block(&mut block);
+    assert_eq!(block, plaintext);
+}
+
+#[test]
+fn test_aes_sbox() {
+    assert_eq!(SBOX[0x00], 0x63);
+    assert_eq!(SBOX[0x53], 0xed);
+    assert_eq!(INV_SBOX[0x63], 0x00);
+}
+
+#[test]
+fn test_aes_key_expansion_128() {
+    let key = hex_decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let round_keys = expand_key(&key).unwrap();
+    assert_eq!(round_keys.len(), 11);
+    assert_eq!(round_keys[0].to_vec(), key);
+    assert_eq!(
+        round_keys[10].to_vec(),
+        hex_decode("d014f9a8c9ee2589e13f0cc8b6630ca6").unwrap()
+    );
+}
+
+#[test]
+fn test_aes_key_expansion_invalid_length() {
+    assert_eq!(
+        expand_key(b"too short").unwrap_err(),
+        AesError::InvalidKeyLength
+    );
+}
+
+#[test]
+fn test_aes_fips197_128() {
+    fips197_block(
+        "000102030405060708090a0b0c0d0e0f",
+        "69c4e0d86a7b0430d8cdb78070b4c55a",
+    );
+}
+
+#[test]
+fn test_aes_fips197_192() {
+    fips197_block(
+        "000102030405060708090a0b0c0d0e0f1011121314151617",
+        "dda97ca4864cdfe06eaf70a0ec0d7191",
+    );
+}
+
+#[test]
+fn test_aes_fips197_256() {
+    fips197_block(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "8ea2b7ca516745bfeafc49904b496089",
+    );
+}
+
+#[cfg(feature = "openssl")]
+#[test]
+fn test_aes_matches_openssl() {
+    let key = b"YELLOW SUBMARINE";
+    let data = b"Build me up buttercup baby just!";
+    let mut crypter = openssl::symm::Crypter::new(
+        openssl::symm::Cipher::aes_128_ecb(),
+        openssl::symm::Mode::Encrypt,
+        key,
+        None,
+    )
+    .unwrap();
+    crypter.pad(false);
+    let mut expected = vec![0u8; data.len() + BLOCK_SIZE];
+    let count = crypter.update(data, &mut expected).unwrap();
+    expected.truncate(count);
+
+    let aes = Aes::new(key).unwrap();
+    let mut encrypted = data.to_vec();
+    for block in encrypted.chunks_exact_mut(BLOCK_SIZE) {
+        aes.encrypt_block(block.try_into().unwrap());
+    }
+    assert_eq!(encrypted, expected);
+}
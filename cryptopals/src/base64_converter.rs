@@ -50,7 +50,7 @@ pub fn base64_encode(bytes: &[u8]) -> String {
 
     //3 bytes generates 4, plus we need to pad so that the encoded data is aligned
     let output_padding = 3 - (remaining_slice.len() % 3);
-    let encoded_len = ((bytes.len() + 2) / 3) * 4;
+    let encoded_len = bytes.len().div_ceil(3) * 4;
     let mut encoded_data = String::with_capacity(encoded_len);
     let mut block4: [u8; 4] = [0, 0, 0, 0];
 
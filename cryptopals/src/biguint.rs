@@ -0,0 +1,853 @@
+use crate::hex_converter::{hex_decode, hex_encode, DecodeError};
+use crate::random::random_bytes;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+
+//Limbs are 32 bits so that a limb product fits in a u64
+const LIMB_BITS: usize = 32;
+
+//Exponent bits handled per multiplication in modpow
+const WINDOW_BITS: usize = 4;
+
+/// Arbitrary precision unsigned integer, stored as little-endian 32-bit limbs with no
+/// leading zero limbs (zero is no limbs at all)
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from_u64(1)
+    }
+
+    pub fn from_u64(n: u64) -> BigUint {
+        BigUint::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> BigUint {
+        let mut n = BigUint { limbs };
+        n.normalize();
+        n
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// The value as a u64 if it fits
+    ///
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(u64::from(self.limbs[0])),
+            2 => Some(u64::from(self.limbs[0]) | u64::from(self.limbs[1]) << 32),
+            _ => None,
+        }
+    }
+
+    /// Big-endian bytes
+    ///
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let limbs = bytes
+            .rchunks(4)
+            .map(|chunk| chunk.iter().fold(0u32, |limb, &b| limb << 8 | u32::from(b)))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Big-endian bytes with no leading zeros (zero is a single zero byte)
+    ///
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes().to_vec())
+            .skip_while(|&b| b == 0)
+            .collect();
+        if bytes.is_empty() {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    /// Big-endian bytes left padded with zeros to len, for fixed size encodings. Values
+    /// that need more than len bytes are returned in full.
+    ///
+    pub fn to_bytes_be_padded(&self, len: usize) -> Vec<u8> {
+        let bytes = self.to_bytes_be();
+        if bytes.len() >= len {
+            return bytes;
+        }
+        let mut padded = vec![0; len - bytes.len()];
+        padded.extend(bytes);
+        padded
+    }
+
+    /// Parse hex, which may be an odd number of digits and may contain whitespace so that
+    /// long constants can be pasted as they are published
+    ///
+    pub fn from_hex(hex: &str) -> std::result::Result<BigUint, DecodeError> {
+        let mut digits: String = hex.chars().filter(|c| !c.is_whitespace()).collect();
+        if !digits.len().is_multiple_of(2) {
+            digits.insert(0, '0');
+        }
+        Ok(BigUint::from_bytes_be(&hex_decode(&digits)?))
+    }
+
+    /// Upper case hex with no leading zeros
+    ///
+    pub fn to_hex(&self) -> String {
+        let hex = hex_encode(&self.to_bytes_be());
+        match hex.trim_start_matches('0') {
+            "" => "0".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.limbs == [1]
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb & 1 == 0)
+    }
+
+    /// Number of bits needed to represent the value
+    ///
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * LIMB_BITS - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Whether bit i (counting from the least significant) is set
+    ///
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / LIMB_BITS)
+            .is_some_and(|limb| limb >> (i % LIMB_BITS) & 1 == 1)
+    }
+
+    /// Subtraction that returns None rather than panicking when other is larger
+    ///
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let rhs = i64::from(other.limbs.get(i).copied().unwrap_or(0));
+            let diff = i64::from(limb) - rhs - borrow;
+            limbs.push(diff as u32);
+            borrow = if diff < 0 { 1 } else { 0 };
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// Quotient and remainder. Panics if the divisor is zero.
+    ///
+    pub fn divrem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "Division by zero");
+        if *self < *divisor {
+            return (BigUint::zero(), self.clone());
+        }
+        if divisor.limbs.len() == 1 {
+            let (quotient, remainder) = self.divrem_limb(divisor.limbs[0]);
+            return (quotient, BigUint::from_u64(u64::from(remainder)));
+        }
+        self.divrem_knuth(divisor)
+    }
+
+    fn divrem_limb(&self, divisor: u32) -> (BigUint, u32) {
+        let divisor = u64::from(divisor);
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = 0u64;
+        for (q, &limb) in quotient.iter_mut().zip(self.limbs.iter()).rev() {
+            let current = remainder << LIMB_BITS | u64::from(limb);
+            *q = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        (BigUint::from_limbs(quotient), remainder as u32)
+    }
+
+    //Knuth's algorithm D (TAOCP 4.3.1). The divisor is shifted so its top limb has the high
+    //bit set, which keeps each estimated quotient limb at most two too large.
+    fn divrem_knuth(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        let shift = divisor.limbs.last().unwrap().leading_zeros() as usize;
+        let v = (divisor << shift).limbs;
+        let mut u = (self << shift).limbs;
+        u.push(0);
+
+        let n = v.len();
+        let m = u.len() - n - 1;
+        let base = 1u64 << LIMB_BITS;
+        let v_top = u64::from(v[n - 1]);
+        let v_next = u64::from(v[n - 2]);
+        let mut quotient = vec![0u32; m + 1];
+
+        for j in (0..=m).rev() {
+            let numerator = u64::from(u[j + n]) << LIMB_BITS | u64::from(u[j + n - 1]);
+            let mut q_hat = numerator / v_top;
+            let mut r_hat = numerator % v_top;
+            while q_hat >= base || q_hat * v_next > (r_hat << LIMB_BITS | u64::from(u[j + n - 2])) {
+                q_hat -= 1;
+                r_hat += v_top;
+                if r_hat >= base {
+                    break;
+                }
+            }
+
+            //u[j..=j + n] -= q_hat * v
+            let mut borrow = 0i64;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let product = q_hat * u64::from(v[i]) + carry;
+                carry = product >> LIMB_BITS;
+                let diff = i64::from(u[i + j]) - borrow - (product & 0xffff_ffff) as i64;
+                u[i + j] = diff as u32;
+                borrow = if diff < 0 { 1 } else { 0 };
+            }
+            let diff = i64::from(u[j + n]) - borrow - carry as i64;
+            u[j + n] = diff as u32;
+
+            //q_hat was one too large, add the divisor back
+            if diff < 0 {
+                q_hat -= 1;
+                let mut carry = 0u64;
+                for i in 0..n {
+                    let sum = u64::from(u[i + j]) + u64::from(v[i]) + carry;
+                    u[i + j] = sum as u32;
+                    carry = sum >> LIMB_BITS;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+            quotient[j] = q_hat as u32;
+        }
+
+        u.truncate(n);
+        let remainder = BigUint::from_limbs(u) >> shift;
+        (BigUint::from_limbs(quotient), remainder)
+    }
+
+    /// self^exponent mod modulus. A fixed window slides over the exponent so that a
+    /// multiplication is only needed every WINDOW_BITS bits, and odd moduli (all the ones
+    /// public key crypto uses) multiply in Montgomery form to avoid dividing.
+    ///
+    pub fn modpow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        assert!(!modulus.is_zero(), "Modulus must not be zero");
+        if modulus.is_one() {
+            return BigUint::zero();
+        }
+
+        let base = self % modulus;
+        if modulus.is_even() {
+            return windowed_pow(exponent, BigUint::one(), base, |a, b| &(a * b) % modulus);
+        }
+
+        let montgomery = Montgomery::new(modulus);
+        let result = windowed_pow(
+            exponent,
+            montgomery.enter(&BigUint::one()),
+            montgomery.enter(&base),
+            |a, b| montgomery.mul(a, b),
+        );
+        montgomery.leave(&result)
+    }
+
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// The inverse of self mod modulus, if they are coprime. Extended Euclid with the
+    /// coefficients kept reduced mod the modulus so they never go negative.
+    ///
+    pub fn modinv(&self, modulus: &BigUint) -> Option<BigUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+        let (mut old_r, mut r) = (self % modulus, modulus.clone());
+        let (mut old_s, mut s) = (BigUint::one(), BigUint::zero());
+        while !r.is_zero() {
+            let (q, remainder) = old_r.divrem(&r);
+            old_r = std::mem::replace(&mut r, remainder);
+            let qs = &(&q * &s) % modulus;
+            let next_s = &(&old_s + modulus) - &qs;
+            old_s = std::mem::replace(&mut s, &next_s % modulus);
+        }
+        if old_r.is_one() {
+            Some(old_s % modulus)
+        } else {
+            None
+        }
+    }
+
+    /// Floor of the nth root, by Newton's method from an overestimate
+    ///
+    pub fn nth_root(&self, n: u32) -> BigUint {
+        assert!(n > 0, "Zeroth root is undefined");
+        if self.is_zero() || n == 1 {
+            return self.clone();
+        }
+
+        let n_big = BigUint::from_u64(u64::from(n));
+        let n_minus_one = BigUint::from_u64(u64::from(n - 1));
+        //2^ceil(bits / n) is at least the root
+        let mut x = BigUint::one() << self.bits().div_ceil(n as usize);
+        loop {
+            let y = &(&(&n_minus_one * &x) + &(self / &x.pow(n - 1))) / &n_big;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// Floor of the cube root
+    ///
+    pub fn cbrt(&self) -> BigUint {
+        self.nth_root(3)
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigUint {
+        let mut result = BigUint::one();
+        for b in (0..32 - exponent.leading_zeros()).rev() {
+            result = &result * &result;
+            if exponent >> b & 1 == 1 {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    /// Uniformly random value in [0, bound), by rejecting random values of the same bit length
+    ///
+    pub fn random_below(bound: &BigUint) -> BigUint {
+        assert!(!bound.is_zero(), "Bound must be positive");
+        let bits = bound.bits();
+        loop {
+            let mut bytes = random_bytes(bits.div_ceil(8));
+            let excess = bytes.len() * 8 - bits;
+            bytes[0] &= 0xff >> excess;
+            let candidate = BigUint::from_bytes_be(&bytes);
+            if candidate < *bound {
+                return candidate;
+            }
+        }
+    }
+
+    /// Uniformly random value in [low, high)
+    ///
+    pub fn random_range(low: &BigUint, high: &BigUint) -> BigUint {
+        low + &BigUint::random_below(&(high - low))
+    }
+}
+
+//Left to right exponentiation with a table of the first 2^WINDOW_BITS powers of the base
+fn windowed_pow<F: Fn(&BigUint, &BigUint) -> BigUint>(
+    exponent: &BigUint,
+    one: BigUint,
+    base: BigUint,
+    mul: F,
+) -> BigUint {
+    let mut table = vec![one.clone(), base];
+    for i in 2..(1 << WINDOW_BITS) {
+        table.push(mul(&table[i - 1], &table[1]));
+    }
+
+    let mut result = one;
+    let windows = exponent.bits().div_ceil(WINDOW_BITS);
+    for w in (0..windows).rev() {
+        for _ in 0..WINDOW_BITS {
+            result = mul(&result, &result);
+        }
+        let index = (0..WINDOW_BITS).rev().fold(0, |index, b| {
+            index << 1 | exponent.bit(w * WINDOW_BITS + b) as usize
+        });
+        if index != 0 {
+            result = mul(&result, &table[index]);
+        }
+    }
+    result
+}
+
+//Montgomery multiplication mod an odd modulus m of n limbs with R = 2^(32n). Numbers are
+//kept as aR mod m, so a product abR^2 only needs dividing by R, which is a shift.
+struct Montgomery {
+    modulus: BigUint,
+    //-m^-1 mod 2^32
+    m_inv: u32,
+}
+
+impl Montgomery {
+    fn new(modulus: &BigUint) -> Montgomery {
+        //Newton's iteration doubles the correct low bits of the inverse each time
+        let m0 = modulus.limbs[0];
+        let mut inv = 1u32;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(m0.wrapping_mul(inv)));
+        }
+        Montgomery {
+            modulus: modulus.clone(),
+            m_inv: inv.wrapping_neg(),
+        }
+    }
+
+    fn enter(&self, a: &BigUint) -> BigUint {
+        &(a << (self.modulus.limbs.len() * LIMB_BITS)) % &self.modulus
+    }
+
+    fn leave(&self, a: &BigUint) -> BigUint {
+        self.mul(a, &BigUint::one())
+    }
+
+    //abR^-1 mod m, interleaving the multiplication with the reduction (CIOS)
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let m = &self.modulus.limbs;
+        let n = m.len();
+        let mut t = vec![0u32; n + 2];
+
+        for i in 0..n {
+            let b_i = u64::from(b.limbs.get(i).copied().unwrap_or(0));
+            let mut carry = 0u64;
+            for (t_j, &a_j) in t.iter_mut().zip(&a.limbs) {
+                let sum = u64::from(*t_j) + u64::from(a_j) * b_i + carry;
+                *t_j = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            for t_j in t[a.limbs.len()..=n].iter_mut() {
+                let sum = u64::from(*t_j) + carry;
+                *t_j = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            t[n + 1] = carry as u32;
+
+            //Add a multiple of m that clears the lowest limb, then shift down a limb
+            let factor = u64::from(t[0].wrapping_mul(self.m_inv));
+            let mut carry = (u64::from(t[0]) + factor * u64::from(m[0])) >> LIMB_BITS;
+            for j in 1..n {
+                let sum = u64::from(t[j]) + factor * u64::from(m[j]) + carry;
+                t[j - 1] = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            let sum = u64::from(t[n]) + carry;
+            t[n - 1] = sum as u32;
+            t[n] = t[n + 1] + (sum >> LIMB_BITS) as u32;
+        }
+
+        t.truncate(n + 1);
+        let result = BigUint::from_limbs(t);
+        if result >= self.modulus {
+            &result - &self.modulus
+        } else {
+            result
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        BigUint::from_u64(n)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Decimal
+///
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        //Peel off nine decimal digits at a time
+        let mut chunks = Vec::new();
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (quotient, remainder) = n.divrem_limb(1_000_000_000);
+            chunks.push(remainder);
+            n = quotient;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let sum = u64::from(self.limbs.get(i).copied().unwrap_or(0))
+                + u64::from(other.limbs.get(i).copied().unwrap_or(0))
+                + carry;
+            limbs.push(sum as u32);
+            carry = sum >> LIMB_BITS;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+/// Panics if other is larger, use checked_sub when that is possible
+///
+impl<'a> Sub<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("Subtraction would be negative")
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> LIMB_BITS;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl<'a> Div<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.divrem(other).0
+    }
+}
+
+impl<'a> Rem<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.divrem(other).1
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        &self + &other
+    }
+}
+
+impl Sub for BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: BigUint) -> BigUint {
+        &self - &other
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl Div for BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: BigUint) -> BigUint {
+        &self / &other
+    }
+}
+
+impl Rem<&BigUint> for BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        &self % other
+    }
+}
+
+impl Rem for BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: BigUint) -> BigUint {
+        &self % &other
+    }
+}
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+        let (limb_shift, bit_shift) = (shift / LIMB_BITS, shift % LIMB_BITS);
+        let mut limbs = vec![0u32; limb_shift];
+        let mut carry = 0u32;
+        for &limb in self.limbs.iter() {
+            if bit_shift == 0 {
+                limbs.push(limb);
+            } else {
+                limbs.push(limb << bit_shift | carry);
+                carry = limb >> (LIMB_BITS - bit_shift);
+            }
+        }
+        limbs.push(carry);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        let (limb_shift, bit_shift) = (shift / LIMB_BITS, shift % LIMB_BITS);
+        if limb_shift >= self.limbs.len() {
+            return BigUint::zero();
+        }
+        let high = &self.limbs[limb_shift..];
+        let limbs = high
+            .iter()
+            .enumerate()
+            .map(|(i, &limb)| {
+                if bit_shift == 0 {
+                    limb
+                } else {
+                    let next = high.get(i + 1).copied().unwrap_or(0);
+                    limb >> bit_shift | next << (LIMB_BITS - bit_shift)
+                }
+            })
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        &self << shift
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        &self >> shift
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+fn hex(s: &str) -> BigUint {
+    BigUint::from_hex(s).unwrap()
+}
+
+#[test]
+fn test_biguint_hex_and_bytes() {
+    let n = hex("0123456789abcdef0123456789ABCDEF");
+    assert_eq!(n.to_hex(), "123456789ABCDEF0123456789ABCDEF");
+    assert_eq!(hex("fff").to_bytes_be(), vec![0x0f, 0xff]);
+    assert_eq!(hex(" 00 01\n02 ").to_bytes_be(), vec![0x01, 0x02]);
+    assert_eq!(BigUint::zero().to_hex(), "0");
+    assert_eq!(BigUint::zero().to_bytes_be(), vec![0]);
+    assert_eq!(
+        BigUint::from_u64(258).to_bytes_be_padded(4),
+        vec![0, 0, 1, 2]
+    );
+    assert_eq!(BigUint::from_bytes_be(&[0, 0, 1, 2]).to_u64(), Some(258));
+    assert!(BigUint::from_hex("xyz").is_err());
+}
+
+#[test]
+fn test_biguint_display() {
+    assert_eq!(BigUint::zero().to_string(), "0");
+    assert_eq!(
+        (BigUint::one() << 100).to_string(),
+        "1267650600228229401496703205376"
+    );
+    assert_eq!(BigUint::from_u64(1_000_000_000).to_string(), "1000000000");
+}
+
+#[test]
+fn test_biguint_add_sub_mul() {
+    let a = hex("FFFFFFFFFFFFFFFFFFFFFFFF");
+    let b = BigUint::one();
+    assert_eq!((&a + &b).to_hex(), "1000000000000000000000000");
+    assert_eq!(&(&a + &b) - &b, a);
+    assert_eq!(b.checked_sub(&a), None);
+    assert_eq!(
+        (&a * &a).to_hex(),
+        "FFFFFFFFFFFFFFFFFFFFFFFE000000000000000000000001"
+    );
+    assert_eq!(&a * &BigUint::zero(), BigUint::zero());
+}
+
+#[test]
+fn test_biguint_divrem() {
+    //Values checked with Python
+    let a = hex("1D8A9C3E5F7B2A4C6E8091B3D5F7193B5D7F91B3D5F70123456789ABCDEF");
+    let b = hex("3B2A19080706F5E4D3C2B1A0");
+    let (q, r) = a.divrem(&b);
+    assert_eq!(q.to_hex(), "7FD2D548D2E855BADDC4AA0DC8CF30A20D6C");
+    assert_eq!(r.to_hex(), "2DA55AE792E1C73AE943BE6F");
+    assert_eq!(&(&q * &b) + &r, a);
+
+    let (q, r) = a.divrem(&BigUint::from_u64(7));
+    assert_eq!(&(&q * &BigUint::from_u64(7)) + &r, a);
+    assert!(r < BigUint::from_u64(7));
+
+    //Hacker's Delight case where the estimated quotient limb is too large and the divisor
+    //has to be added back
+    let (q, r) = hex("800000000000FFFE00000000").divrem(&hex("800000000000FFFF"));
+    assert_eq!(q.to_hex(), "FFFFFFFF");
+    assert_eq!(r.to_hex(), "7FFFFFFF0000FFFF");
+}
+
+#[test]
+fn test_biguint_divrem_random() {
+    for _ in 0..200 {
+        let a = BigUint::from_bytes_be(&random_bytes(crate::random::random_range(1, 80)));
+        let b = BigUint::from_bytes_be(&random_bytes(crate::random::random_range(1, 40)));
+        if b.is_zero() {
+            continue;
+        }
+        let (q, r) = a.divrem(&b);
+        assert!(r < b);
+        assert_eq!(&(&q * &b) + &r, a);
+    }
+}
+
+#[test]
+fn test_biguint_shifts() {
+    let a = hex("123456789ABCDEF");
+    assert_eq!((&a << 36).to_hex(), "123456789ABCDEF000000000");
+    assert_eq!(&(&a << 67) >> 67, a);
+    assert_eq!((&a >> 8).to_hex(), "123456789ABCD");
+    assert_eq!(&a >> 200, BigUint::zero());
+}
+
+#[test]
+fn test_biguint_modpow() {
+    //Values checked with Python's pow
+    let base = hex("A5F3B2C1D0E9F8071625344352617080");
+    let exponent = hex("F1E2D3C4B5A69788796A5B4C3D2E1F00FF");
+    let modulus =
+        hex("C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DD");
+    assert_eq!(
+        base.modpow(&exponent, &modulus).to_hex(),
+        "61E96B78239F737F23266CE8BBBD91B679D316EBB013ECC4D8DD9F425BD0321A7FFF707095A904D6"
+    );
+    assert_eq!(
+        BigUint::from_u64(4).modpow(&BigUint::from_u64(13), &BigUint::from_u64(497)),
+        BigUint::from_u64(445)
+    );
+    assert_eq!(base.modpow(&BigUint::zero(), &modulus), BigUint::one());
+    //Even modulus, which can't use Montgomery form
+    assert_eq!(
+        BigUint::from_u64(3).modpow(&BigUint::from_u64(200), &BigUint::from_u64(1 << 40)),
+        BigUint::from_u64(131_717_443_745)
+    );
+    assert_eq!(base.modpow(&exponent, &BigUint::one()), BigUint::zero());
+}
+
+#[test]
+fn test_biguint_gcd_modinv() {
+    assert_eq!(
+        BigUint::from_u64(462).gcd(&BigUint::from_u64(1071)),
+        BigUint::from_u64(21)
+    );
+    assert_eq!(
+        BigUint::from_u64(17).modinv(&BigUint::from_u64(3120)),
+        Some(BigUint::from_u64(2753))
+    );
+    assert_eq!(BigUint::from_u64(6).modinv(&BigUint::from_u64(9)), None);
+
+    let modulus =
+        hex("C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DD");
+    let a = hex("A5F3B2C1D0E9F8071625344352617080");
+    let inverse = a.modinv(&modulus).unwrap();
+    assert_eq!(&(&a * &inverse) % &modulus, BigUint::one());
+}
+
+#[test]
+fn test_biguint_roots() {
+    let root = hex("1F2E3D4C5B6A79880123456789");
+    let cube = root.pow(3);
+    assert_eq!(cube.cbrt(), root);
+    assert_eq!((&cube + &BigUint::one()).cbrt(), root);
+    assert_eq!((&cube - &BigUint::one()).cbrt(), &root - &BigUint::one());
+    assert_eq!(
+        BigUint::from_u64(1 << 20).nth_root(5),
+        BigUint::from_u64(16)
+    );
+    assert_eq!(BigUint::from_u64(80).nth_root(2), BigUint::from_u64(8));
+    assert_eq!(BigUint::zero().cbrt(), BigUint::zero());
+}
+
+#[test]
+fn test_biguint_random_below() {
+    let bound = hex("10000000000000001");
+    for _ in 0..100 {
+        assert!(BigUint::random_below(&bound) < bound);
+    }
+    assert_eq!(BigUint::random_below(&BigUint::one()), BigUint::zero());
+    let low = BigUint::from_u64(1000);
+    let high = BigUint::from_u64(1010);
+    let r = BigUint::random_range(&low, &high);
+    assert!(r >= low && r < high);
+}
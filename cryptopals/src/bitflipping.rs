@@ -0,0 +1,233 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::modes::{cbc_decrypt, cbc_encrypt, ctr, Counter};
+use crate::random::random_bytes;
+use crate::xor_encrypter::xor_bytes;
+
+const COMMENT_PREFIX: &str = "comment1=cooking%20MCs;userdata=";
+const COMMENT_SUFFIX: &str = ";comment2=%20like%20a%20pound%20of%20bacon";
+const ADMIN_MARKER: &[u8] = b";admin=true;";
+
+/// The XOR mask that turns the known plaintext into the desired plaintext when applied to
+/// the bytes that are XOR'd with it (the previous block in CBC)
+///
+pub fn flip_mask(known: &[u8], desired: &[u8]) -> Result<Vec<u8>> {
+    if known.len() != desired.len() {
+        return Err(CryptoError::NoSolution(
+            "known and desired plaintext differ in length",
+        ));
+    }
+    Ok(xor_bytes(known, desired))
+}
+
+/// XOR the mask into the ciphertext starting at the given offset
+///
+pub fn apply_mask(ciphertext: &mut [u8], offset: usize, mask: &[u8]) -> Result<()> {
+    let target = ciphertext
+        .get_mut(offset..offset + mask.len())
+        .ok_or(CryptoError::NoSolution(
+            "mask runs past the end of the ciphertext",
+        ))?;
+    for (c, m) in target.iter_mut().zip(mask.iter()) {
+        *c ^= m;
+    }
+    Ok(())
+}
+
+/// Change plaintext in a CBC ciphertext without the key. Flipping a bit in one ciphertext
+/// block flips the same bit of the next block's plaintext (and scrambles the block itself).
+/// The offset is of the known plaintext and it must not be in the first block.
+///
+pub fn cbc_bitflip(
+    ciphertext: &mut [u8],
+    plaintext_offset: usize,
+    known: &[u8],
+    desired: &[u8],
+) -> Result<()> {
+    if plaintext_offset < BLOCK_SIZE {
+        return Err(CryptoError::NoSolution(
+            "cannot flip the first block without the IV",
+        ));
+    }
+    apply_mask(
+        ciphertext,
+        plaintext_offset - BLOCK_SIZE,
+        &flip_mask(known, desired)?,
+    )
+}
+
+/// Change plaintext in a CTR ciphertext without the key. Each ciphertext byte is just the
+/// plaintext byte XOR'd with the keystream so flipping a bit flips the same plaintext bit.
+///
+pub fn ctr_bitflip(
+    ciphertext: &mut [u8],
+    plaintext_offset: usize,
+    known: &[u8],
+    desired: &[u8],
+) -> Result<()> {
+    apply_mask(ciphertext, plaintext_offset, &flip_mask(known, desired)?)
+}
+
+/// Block cipher mode the comment service encrypts its cookies with
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CommentMode {
+    Cbc,
+    Ctr,
+}
+
+/// Encrypts user comments into a cookie, quoting out the characters that would let the user
+/// write their own fields
+///
+pub struct CommentService {
+    cipher: Aes,
+    mode: CommentMode,
+    //The CBC IV or the CTR nonce
+    iv: Vec<u8>,
+}
+
+impl CommentService {
+    pub fn new() -> CommentService {
+        CommentService::with_mode(CommentMode::Cbc)
+    }
+
+    pub fn with_mode(mode: CommentMode) -> CommentService {
+        let iv = match mode {
+            CommentMode::Cbc => random_bytes(BLOCK_SIZE),
+            //Random nonce in the first half, block count in the second
+            CommentMode::Ctr => {
+                let mut nonce = random_bytes(BLOCK_SIZE / 2);
+                nonce.resize(BLOCK_SIZE, 0);
+                nonce
+            }
+        };
+        CommentService {
+            cipher: Aes::new(&random_bytes(BLOCK_SIZE)).unwrap(),
+            mode,
+            iv,
+        }
+    }
+
+    /// Offset in the plaintext at which the user data starts
+    ///
+    pub fn userdata_offset() -> usize {
+        COMMENT_PREFIX.len()
+    }
+
+    /// Quote the user data and encrypt it inside the comment cookie
+    ///
+    pub fn encrypt_userdata(&self, userdata: &str) -> Result<Vec<u8>> {
+        let plaintext = COMMENT_PREFIX.to_string() + &quote(userdata) + COMMENT_SUFFIX;
+        let encrypted = match self.mode {
+            CommentMode::Cbc => cbc_encrypt(&self.cipher, &self.iv, plaintext.as_bytes())?,
+            CommentMode::Ctr => ctr(
+                &self.cipher,
+                &self.iv,
+                Counter::LittleEndianHalf,
+                plaintext.as_bytes(),
+            )?,
+        };
+        Ok(encrypted)
+    }
+
+    /// Decrypt the cookie and check whether it grants admin
+    ///
+    pub fn is_admin(&self, encrypted: &[u8]) -> Result<bool> {
+        let decrypted = match self.mode {
+            CommentMode::Cbc => cbc_decrypt(&self.cipher, &self.iv, encrypted)?,
+            CommentMode::Ctr => ctr(&self.cipher, &self.iv, Counter::LittleEndianHalf, encrypted)?,
+        };
+        Ok(decrypted
+            .windows(ADMIN_MARKER.len())
+            .any(|w| w == ADMIN_MARKER))
+    }
+}
+
+impl Default for CommentService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote the metacharacters so the user data can't introduce its own fields
+///
+pub fn quote(userdata: &str) -> String {
+    userdata.replace(';', "%3B").replace('=', "%3D")
+}
+
+/// Make a cookie that grants admin by bitflipping. A sacrificial block of user data is
+/// followed by harmless text the same length as ";admin=true;", which the flipped sacrificial
+/// block turns into the marker.
+///
+pub fn forge_admin_cbc(service: &CommentService) -> Result<Vec<u8>> {
+    let offset = CommentService::userdata_offset();
+    let align = (BLOCK_SIZE - offset % BLOCK_SIZE) % BLOCK_SIZE;
+    let known = vec![b'A'; ADMIN_MARKER.len()];
+
+    let userdata = "A".repeat(align + BLOCK_SIZE + known.len());
+    let mut encrypted = service.encrypt_userdata(&userdata)?;
+
+    let target_offset = offset + align + BLOCK_SIZE;
+    cbc_bitflip(&mut encrypted, target_offset, &known, ADMIN_MARKER)?;
+    Ok(encrypted)
+}
+
+/// Make a cookie that grants admin by bitflipping CTR. The user data is harmless text the
+/// same length as ";admin=true;" which is flipped into the marker in place.
+///
+pub fn forge_admin_ctr(service: &CommentService) -> Result<Vec<u8>> {
+    let known = vec![b'A'; ADMIN_MARKER.len()];
+    let userdata = String::from_utf8(known.clone()).unwrap();
+    let mut encrypted = service.encrypt_userdata(&userdata)?;
+
+    ctr_bitflip(
+        &mut encrypted,
+        CommentService::userdata_offset(),
+        &known,
+        ADMIN_MARKER,
+    )?;
+    Ok(encrypted)
+}
+
+/// Unit tests
+///
+#[test]
+fn test_comment_service_quotes_metacharacters() {
+    let service = CommentService::new();
+    let encrypted = service.encrypt_userdata(";admin=true;").unwrap();
+    assert!(!service.is_admin(&encrypted).unwrap());
+    assert_eq!(quote(";admin=true;"), "%3Badmin%3Dtrue%3B");
+}
+
+#[test]
+fn test_flip_mask() {
+    let mask = flip_mask(b"AAAA", b";a=;").unwrap();
+    let mut data = b"AAAA".to_vec();
+    apply_mask(&mut data, 0, &mask).unwrap();
+    assert_eq!(data, b";a=;");
+    assert!(flip_mask(b"AAAA", b";a=").is_err());
+    assert!(ctr_bitflip(&mut data, 0, b"AAAA", b";a=").is_err());
+}
+
+#[test]
+fn test_apply_mask_out_of_range() {
+    let mut data = vec![0; 4];
+    assert!(apply_mask(&mut data, 2, &[1, 1, 1]).is_err());
+}
+
+#[test]
+fn test_forge_admin_cbc() {
+    let service = CommentService::new();
+    let forged = forge_admin_cbc(&service).unwrap();
+    assert!(service.is_admin(&forged).unwrap());
+}
+
+#[test]
+fn test_forge_admin_ctr() {
+    let service = CommentService::with_mode(CommentMode::Ctr);
+    let encrypted = service.encrypt_userdata(";admin=true;").unwrap();
+    assert!(!service.is_admin(&encrypted).unwrap());
+
+    let forged = forge_admin_ctr(&service).unwrap();
+    assert!(service.is_admin(&forged).unwrap());
+}
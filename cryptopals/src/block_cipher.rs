@@ -0,0 +1,33 @@
+use crate::aes::{self, Aes};
+use std::convert::TryInto;
+
+/// A cipher that encrypts and decrypts fixed size blocks in place.
+/// The modes of operation are written against this so they work with any block cipher.
+///
+pub trait BlockCipher {
+    /// Size of a block in bytes
+    ///
+    fn block_size(&self) -> usize;
+
+    /// Encrypt a single block in place. The block must be exactly block_size() bytes.
+    ///
+    fn encrypt_block(&self, block: &mut [u8]);
+
+    /// Decrypt a single block in place. The block must be exactly block_size() bytes.
+    ///
+    fn decrypt_block(&self, block: &mut [u8]);
+}
+
+impl BlockCipher for Aes {
+    fn block_size(&self) -> usize {
+        aes::BLOCK_SIZE
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        Aes::encrypt_block(self, block.try_into().expect("AES block must be 16 bytes"));
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        Aes::decrypt_block(self, block.try_into().expect("AES block must be 16 bytes"));
+    }
+}
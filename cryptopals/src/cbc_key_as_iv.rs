@@ -0,0 +1,171 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::modes::{cbc_decrypt_with, cbc_encrypt, ModeError};
+use crate::padding::{pkcs7_unpad, NoPadding};
+use crate::random::random_bytes;
+use crate::xor_encrypter::xor_bytes;
+use std::error::Error;
+use std::fmt;
+
+/// Rejection from the key-as-IV service. The error carries the decrypted plaintext so that
+/// whoever sent the message can see what was wrong with it.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsciiError {
+    HighAscii(Vec<u8>),
+    Mode(ModeError),
+}
+
+impl Error for AsciiError {
+    fn description(&self) -> &str {
+        match *self {
+            AsciiError::HighAscii(_) => "Plaintext contains high-ASCII characters",
+            AsciiError::Mode(_) => "Ciphertext could not be decrypted",
+        }
+    }
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AsciiError::HighAscii(ref plaintext) => write!(
+                f,
+                "Plaintext contains high-ASCII characters: {}",
+                String::from_utf8_lossy(plaintext)
+            ),
+            AsciiError::Mode(ref e) => write!(f, "Ciphertext could not be decrypted: {}", e),
+        }
+    }
+}
+
+impl From<ModeError> for AsciiError {
+    fn from(e: ModeError) -> Self {
+        AsciiError::Mode(e)
+    }
+}
+
+/// Encrypts with CBC reusing the key as the IV, and complains about invalid messages by
+/// returning their plaintext
+///
+pub struct KeyAsIvService {
+    key: Vec<u8>,
+}
+
+impl KeyAsIvService {
+    pub fn new() -> KeyAsIvService {
+        KeyAsIvService {
+            key: random_bytes(BLOCK_SIZE),
+        }
+    }
+
+    pub fn with_key(key: [u8; BLOCK_SIZE]) -> KeyAsIvService {
+        KeyAsIvService { key: key.to_vec() }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let cipher = Aes::new(&self.key).unwrap();
+        cbc_encrypt(&cipher, &self.key, plaintext).unwrap()
+    }
+
+    /// Decrypt and check the message is plain ASCII. Invalid padding is left in place rather
+    /// than rejected, as a lenient service might.
+    ///
+    pub fn check_ascii(&self, ciphertext: &[u8]) -> std::result::Result<(), AsciiError> {
+        let cipher = Aes::new(&self.key).unwrap();
+        let mut plaintext = cbc_decrypt_with(&cipher, &NoPadding, &self.key, ciphertext)?;
+        if let Ok(unpadded) = pkcs7_unpad(&plaintext, BLOCK_SIZE) {
+            let len = unpadded.len();
+            plaintext.truncate(len);
+        }
+        if plaintext.iter().any(|&b| b > 0x7f) {
+            return Err(AsciiError::HighAscii(plaintext));
+        }
+        Ok(())
+    }
+
+    /// Whether the recovered key is the one the service is using
+    ///
+    pub fn is_key(&self, key: &[u8]) -> bool {
+        self.key == key
+    }
+}
+
+impl Default for KeyAsIvService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recover the key of a service that uses it as the IV. Sending C1 || 0 || C1 decrypts the
+/// first block to D(C1) ^ key and the third to D(C1) ^ 0, so XORing the two gives the key.
+///
+pub fn recover_key_as_iv<F: Fn(&[u8]) -> std::result::Result<(), AsciiError>>(
+    check_ascii: F,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    if ciphertext.len() < 3 * BLOCK_SIZE {
+        return Err(CryptoError::NoSolution(
+            "need a ciphertext of at least three blocks",
+        ));
+    }
+
+    let first = &ciphertext[..BLOCK_SIZE];
+    let mut modified = first.to_vec();
+    modified.extend(&[0u8; BLOCK_SIZE]);
+    modified.extend(first);
+    modified.extend(&ciphertext[3 * BLOCK_SIZE..]);
+
+    //The third block is random garbage, so a lenient service may have taken its end as
+    //padding and stripped it
+    match check_ascii(&modified) {
+        Err(AsciiError::HighAscii(plaintext)) => {
+            match plaintext.get(2 * BLOCK_SIZE..3 * BLOCK_SIZE) {
+                Some(third) => Ok(xor_bytes(&plaintext[..BLOCK_SIZE], third)),
+                None => Err(CryptoError::NoSolution(
+                    "returned plaintext is missing the third block",
+                )),
+            }
+        }
+        Ok(()) => Err(CryptoError::NoSolution(
+            "modified ciphertext decrypted to plain ASCII",
+        )),
+        Err(AsciiError::Mode(e)) => Err(e.into()),
+    }
+}
+
+/// Unit tests
+///
+#[test]
+fn test_check_ascii() {
+    let service = KeyAsIvService::new();
+    let encrypted = service.encrypt(b"All plain ASCII here, nothing to see");
+    assert_eq!(service.check_ascii(&encrypted), Ok(()));
+
+    let encrypted = service.encrypt(b"Caf\xc3\xa9");
+    assert_eq!(
+        service.check_ascii(&encrypted),
+        Err(AsciiError::HighAscii(b"Caf\xc3\xa9".to_vec()))
+    );
+}
+
+#[test]
+fn test_recover_key_as_iv() {
+    let service = KeyAsIvService::new();
+    let encrypted = service.encrypt(&[b'A'; 3 * BLOCK_SIZE]);
+    let key = recover_key_as_iv(|ct| service.check_ascii(ct), &encrypted).unwrap();
+    assert!(service.is_key(&key));
+
+    assert!(recover_key_as_iv(|ct| service.check_ascii(ct), &encrypted[..32]).is_err());
+    assert!(service.check_ascii(&encrypted[..20]).is_err());
+}
+
+#[test]
+fn test_recover_key_as_iv_short_plaintext() {
+    //A three block ciphertext whose last block happened to look like padding and was stripped
+    let encrypted = [0u8; 3 * BLOCK_SIZE];
+    let check_ascii = |_: &[u8]| Err(AsciiError::HighAscii(vec![0xff; 3 * BLOCK_SIZE - 2]));
+    assert!(matches!(
+        recover_key_as_iv(check_ascii, &encrypted),
+        Err(CryptoError::NoSolution(_))
+    ));
+}
@@ -0,0 +1,388 @@
+use crate::error::{CryptoError, Result};
+use crate::{set1, set2, set3, set4, set5};
+use std::fmt;
+
+/// The known answer to a challenge. Long answers are stored as a hash rather than inline.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Expected {
+    Exact(&'static str),
+    Fnv1a(u64),
+}
+
+impl Expected {
+    /// Whether the given answer is the known correct one
+    ///
+    pub fn matches(&self, answer: &str) -> bool {
+        match *self {
+            Expected::Exact(expected) => answer == expected,
+            Expected::Fnv1a(hash) => fnv1a(answer.as_bytes()) == hash,
+        }
+    }
+}
+
+/// A registered challenge and the function that solves it
+///
+#[derive(Debug, Copy, Clone)]
+pub struct Challenge {
+    pub set: u32,
+    pub number: u32,
+    pub title: &'static str,
+    pub solve: fn() -> Result<String>,
+    pub expected: Expected,
+}
+
+/// Why a challenge failed verification
+///
+#[derive(Debug)]
+pub enum Failure {
+    WrongAnswer(String),
+    Error(CryptoError),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::WrongAnswer(answer) => write!(f, "wrong answer {:?}", answer),
+            Failure::Error(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Challenge {
+    /// Solve the challenge and check the answer against the known answer
+    ///
+    pub fn verify(&self) -> std::result::Result<String, Failure> {
+        let answer = (self.solve)().map_err(Failure::Error)?;
+        if self.expected.matches(&answer) {
+            Ok(answer)
+        } else {
+            Err(Failure::WrongAnswer(answer))
+        }
+    }
+}
+
+//Secret recovered by the byte-at-a-time ECB challenges
+const ROLLIN: &str = "Rollin' in my 5.0\nWith my rag-top down so my hair can blow\nThe girlies on standby waving just to say hi\nDid you stop? No, I just drove by\n";
+
+/// Every challenge that has a solver, ordered by challenge number
+///
+pub static CHALLENGES: &[Challenge] = &[
+    Challenge {
+        set: 1,
+        number: 1,
+        title: "Convert hex to base64",
+        solve: set1::challenge1::solve,
+        expected: Expected::Exact(
+            "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t",
+        ),
+    },
+    Challenge {
+        set: 1,
+        number: 2,
+        title: "Fixed XOR",
+        solve: set1::challenge2::solve,
+        expected: Expected::Exact("746865206B696420646F6E277420706C6179"),
+    },
+    Challenge {
+        set: 1,
+        number: 3,
+        title: "Single-byte XOR cipher",
+        solve: set1::challenge3::solve,
+        expected: Expected::Exact("Cooking MC's like a pound of bacon"),
+    },
+    Challenge {
+        set: 1,
+        number: 4,
+        title: "Detect single-character XOR",
+        solve: set1::challenge4::solve,
+        expected: Expected::Exact("Now that the party is jumping\n"),
+    },
+    Challenge {
+        set: 1,
+        number: 5,
+        title: "Implement repeating-key XOR",
+        solve: set1::challenge5::solve,
+        expected: Expected::Exact("0B3637272A2B2E63622C2E69692A23693A2A3C6324202D623D63343C2A26226324272765272A282B2F20430A652E2C652A3124333A653E2B2027630C692B20283165286326302E27282F"),
+    },
+    Challenge {
+        set: 1,
+        number: 6,
+        title: "Break repeating-key XOR",
+        solve: set1::challenge6::solve,
+        expected: Expected::Fnv1a(0x6755_9bda_eacc_9941),
+    },
+    Challenge {
+        set: 1,
+        number: 7,
+        title: "AES in ECB mode",
+        solve: set1::challenge7::solve,
+        expected: Expected::Fnv1a(0x6755_9bda_eacc_9941),
+    },
+    Challenge {
+        set: 1,
+        number: 8,
+        title: "Detect AES in ECB mode",
+        solve: set1::challenge8::solve,
+        expected: Expected::Exact("d880619740a8a19b7840a8a31c810a3d08649af70dc06f4fd5d2d69c744cd283e2dd052f6b641dbf9d11b0348542bb5708649af70dc06f4fd5d2d69c744cd2839475c9dfdbc1d46597949d9c7e82bf5a08649af70dc06f4fd5d2d69c744cd28397a93eab8d6aecd566489154789a6b0308649af70dc06f4fd5d2d69c744cd283d403180c98c8f6db1f2a3f9c4040deb0ab51b29933f2c123c58386b06fba186a"),
+    },
+    Challenge {
+        set: 2,
+        number: 9,
+        title: "Implement PKCS#7 padding",
+        solve: set2::challenge9::solve,
+        expected: Expected::Exact("59454C4C4F57205355424D4152494E4504040404"),
+    },
+    Challenge {
+        set: 2,
+        number: 10,
+        title: "Implement CBC mode",
+        solve: set2::challenge10::solve,
+        expected: Expected::Fnv1a(0x6755_9bda_eacc_9941),
+    },
+    Challenge {
+        set: 2,
+        number: 11,
+        title: "An ECB/CBC detection oracle",
+        solve: set2::challenge11::solve,
+        expected: Expected::Exact("Detected ECB/CBC correctly in 1000 of 1000 trials"),
+    },
+    Challenge {
+        set: 2,
+        number: 12,
+        title: "Byte-at-a-time ECB decryption (Simple)",
+        solve: set2::challenge12::solve,
+        expected: Expected::Exact(ROLLIN),
+    },
+    Challenge {
+        set: 2,
+        number: 13,
+        title: "ECB cut-and-paste",
+        solve: set2::challenge13::solve,
+        expected: Expected::Exact("email=admin@bar.com&uid=10&role=admin"),
+    },
+    Challenge {
+        set: 2,
+        number: 14,
+        title: "Byte-at-a-time ECB decryption (Harder)",
+        solve: set2::challenge14::solve,
+        expected: Expected::Exact(ROLLIN),
+    },
+    Challenge {
+        set: 2,
+        number: 15,
+        title: "PKCS#7 padding validation",
+        solve: set2::challenge15::solve,
+        expected: Expected::Exact("ICE ICE BABY"),
+    },
+    Challenge {
+        set: 2,
+        number: 16,
+        title: "CBC bitflipping attacks",
+        solve: set2::challenge16::solve,
+        expected: Expected::Exact("admin=true"),
+    },
+    Challenge {
+        set: 3,
+        number: 17,
+        title: "The CBC padding oracle",
+        solve: set3::challenge17::solve,
+        expected: Expected::Fnv1a(0xd947_d2bc_8944_3b4f),
+    },
+    Challenge {
+        set: 3,
+        number: 19,
+        title: "Break fixed-nonce CTR mode using substitutions",
+        solve: set3::challenge19::solve,
+        expected: Expected::Fnv1a(0xb214_d6c6_06df_1de4),
+    },
+    Challenge {
+        set: 3,
+        number: 21,
+        title: "Implement the MT19937 Mersenne Twister RNG",
+        solve: set3::challenge21::solve,
+        expected: Expected::Exact("3499211612 581869302 3890346734 3586334585 545404204"),
+    },
+    Challenge {
+        set: 3,
+        number: 22,
+        title: "Crack an MT19937 seed",
+        solve: set3::challenge22::solve,
+        expected: Expected::Exact("1539000000"),
+    },
+    Challenge {
+        set: 3,
+        number: 23,
+        title: "Clone an MT19937 RNG from its output",
+        solve: set3::challenge23::solve,
+        expected: Expected::Exact("Predicted 1000 of 1000 outputs"),
+    },
+    Challenge {
+        set: 3,
+        number: 24,
+        title: "Create the MT19937 stream cipher and break it",
+        solve: set3::challenge24::solve,
+        expected: Expected::Exact("24301 1539000000"),
+    },
+    Challenge {
+        set: 4,
+        number: 25,
+        title: "Break \"random access read/write\" AES CTR",
+        solve: set4::challenge25::solve,
+        expected: Expected::Fnv1a(0x6755_9bda_eacc_9941),
+    },
+    Challenge {
+        set: 4,
+        number: 26,
+        title: "CTR bitflipping",
+        solve: set4::challenge26::solve,
+        expected: Expected::Exact("admin=true"),
+    },
+    Challenge {
+        set: 4,
+        number: 27,
+        title: "Recover the key from CBC with IV=Key",
+        solve: set4::challenge27::solve,
+        expected: Expected::Exact("2B7E151628AED2A6ABF7158809CF4F3C"),
+    },
+    Challenge {
+        set: 4,
+        number: 28,
+        title: "Implement a SHA-1 keyed MAC",
+        solve: set4::challenge28::solve,
+        expected: Expected::Exact("08CB9F974E3141954F5B09A648FAC55F20427D57"),
+    },
+    Challenge {
+        set: 4,
+        number: 29,
+        title: "Break a SHA-1 keyed MAC using length extension",
+        solve: set4::challenge29::solve,
+        expected: Expected::Exact("40A7D34F134CC7438645F51172ED8A787470EA89"),
+    },
+    Challenge {
+        set: 4,
+        number: 30,
+        title: "Break an MD4 keyed MAC using length extension",
+        solve: set4::challenge30::solve,
+        expected: Expected::Exact("406F26E4CEC0F2FA66A8893C6B0B4341"),
+    },
+    Challenge {
+        set: 4,
+        number: 31,
+        title: "Implement and break HMAC-SHA1 with an artificial timing leak",
+        solve: set4::challenge31::solve,
+        expected: Expected::Exact("274B7C4D98605FCF739A0BF9237551623F415FB8"),
+    },
+    Challenge {
+        set: 4,
+        number: 32,
+        title: "Break HMAC-SHA1 with a slightly less artificial timing leak",
+        solve: set4::challenge32::solve,
+        expected: Expected::Exact("274B7C4D98605FCF739A0BF9237551623F415FB8"),
+    },
+    Challenge {
+        set: 5,
+        number: 33,
+        title: "Implement Diffie-Hellman",
+        solve: set5::challenge33::solve,
+        expected: Expected::Exact("C4F6A3F50E76ECB63A827169E1F39E52"),
+    },
+    Challenge {
+        set: 5,
+        number: 34,
+        title: "Implement a MITM key-fixing attack on Diffie-Hellman with parameter injection",
+        solve: set5::challenge34::solve,
+        expected: Expected::Exact("Meet me at the usual place"),
+    },
+    Challenge {
+        set: 5,
+        number: 35,
+        title: "Implement DH with negotiated groups, and break with malicious \"g\" parameters",
+        solve: set5::challenge35::solve,
+        expected: Expected::Exact("Meet me at the usual place"),
+    },
+    Challenge {
+        set: 5,
+        number: 36,
+        title: "Implement Secure Remote Password (SRP)",
+        solve: set5::challenge36::solve,
+        expected: Expected::Exact("C2DB54A4E932E9BDC8BB5D520AB7200D65FDE033E2976180CA2BB39593B8CFC4"),
+    },
+    Challenge {
+        set: 5,
+        number: 37,
+        title: "Break SRP with a zero key",
+        solve: set5::challenge37::solve,
+        expected: Expected::Exact("6E340B9CFFB37A989CA544E6BB780A2C78901D3FB33738768511A30617AFA01D"),
+    },
+    Challenge {
+        set: 5,
+        number: 38,
+        title: "Offline dictionary attack on simplified SRP",
+        solve: set5::challenge38::solve,
+        expected: Expected::Exact("trustno1"),
+    },
+    Challenge {
+        set: 5,
+        number: 39,
+        title: "Implement RSA",
+        solve: set5::challenge39::solve,
+        expected: Expected::Exact("Textbook RSA"),
+    },
+    Challenge {
+        set: 5,
+        number: 40,
+        title: "Implement an E=3 RSA Broadcast attack",
+        solve: set5::challenge40::solve,
+        expected: Expected::Exact("The same message to three recipients"),
+    },
+];
+
+/// 64 bit FNV-1a hash used to store long answers compactly
+///
+pub fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Find a challenge by its number
+///
+pub fn find(number: u32) -> Option<&'static Challenge> {
+    CHALLENGES.iter().find(|c| c.number == number)
+}
+
+/// All the challenges belonging to the given set
+///
+pub fn in_set(set: u32) -> impl Iterator<Item = &'static Challenge> {
+    CHALLENGES.iter().filter(move |c| c.set == set)
+}
+
+/// Unit tests
+///
+#[test]
+fn test_challenges_ordered_and_unique() {
+    assert!(CHALLENGES.windows(2).all(|w| w[0].number < w[1].number));
+    assert!(CHALLENGES.windows(2).all(|w| w[0].set <= w[1].set));
+}
+
+#[test]
+fn test_challenges_expected_matches() {
+    assert!(Expected::Exact("abc").matches("abc"));
+    assert!(!Expected::Exact("abc").matches("abd"));
+    assert!(Expected::Fnv1a(0xe71f_a219_0541_574b).matches("abc"));
+    assert!(!Expected::Fnv1a(0xe71f_a219_0541_574b).matches("abd"));
+}
+
+#[test]
+fn test_challenges_find() {
+    assert_eq!(find(6).unwrap().set, 1);
+    assert_eq!(find(9).unwrap().set, 2);
+    assert!(find(0).is_none());
+}
+
+#[test]
+fn test_challenges_in_set() {
+    assert_eq!(in_set(1).count(), 8);
+    assert!(in_set(2).all(|c| c.set == 2));
+}
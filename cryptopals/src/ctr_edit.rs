@@ -0,0 +1,108 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::modes::{ctr, ctr_at, Counter};
+use crate::random::random_bytes;
+use crate::xor_encrypter::xor_bytes;
+
+//The nonce is fixed (zero) so the key alone determines the keystream
+const NONCE: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
+
+/// Replace the plaintext at the offset in a CTR ciphertext with new text, re-encrypting only
+/// the edited range. The edit may extend the ciphertext but must start within it.
+///
+pub fn edit(ciphertext: &[u8], key: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>> {
+    if offset > ciphertext.len() {
+        return Err(CryptoError::NoSolution(
+            "edit starts past the end of the ciphertext",
+        ));
+    }
+    let cipher = Aes::new(key)?;
+    let encrypted = ctr_at(&cipher, &NONCE, Counter::LittleEndianHalf, offset, newtext)?;
+
+    let mut edited = ciphertext.to_vec();
+    edited.truncate(offset);
+    edited.extend(&encrypted);
+    if let Some(rest) = ciphertext.get(offset + newtext.len()..) {
+        edited.extend(rest);
+    }
+    Ok(edited)
+}
+
+/// Holds a secret key and exposes the edit function to callers that only have the ciphertext
+///
+pub struct EditService {
+    key: Vec<u8>,
+}
+
+impl EditService {
+    pub fn new() -> EditService {
+        EditService {
+            key: random_bytes(BLOCK_SIZE),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let cipher = Aes::new(&self.key)?;
+        Ok(ctr(&cipher, &NONCE, Counter::LittleEndianHalf, plaintext)?)
+    }
+
+    pub fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>> {
+        edit(ciphertext, &self.key, offset, newtext)
+    }
+}
+
+impl Default for EditService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recover the plaintext of a CTR ciphertext given an edit function. Editing the whole
+/// message to zeros gives back the keystream itself.
+///
+pub fn recover_with_edit<F: Fn(&[u8], usize, &[u8]) -> Result<Vec<u8>>>(
+    edit: F,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    let keystream = edit(ciphertext, 0, &vec![0; ciphertext.len()])?;
+    Ok(xor_bytes(ciphertext, &keystream))
+}
+
+/// Unit tests
+///
+#[test]
+fn test_edit() {
+    let key = b"YELLOW SUBMARINE";
+    let cipher = Aes::new(key).unwrap();
+    let plaintext = b"The quick brown fox jumps over the lazy dog";
+    let encrypted = ctr(&cipher, &NONCE, Counter::LittleEndianHalf, plaintext).unwrap();
+
+    let edited = edit(&encrypted, key, 10, b"green").unwrap();
+    let decrypted = ctr(&cipher, &NONCE, Counter::LittleEndianHalf, &edited).unwrap();
+    assert_eq!(
+        decrypted,
+        &b"The quick green fox jumps over the lazy dog"[..]
+    );
+
+    let edited = edit(&encrypted, key, 42, b"gs and cats").unwrap();
+    let decrypted = ctr(&cipher, &NONCE, Counter::LittleEndianHalf, &edited).unwrap();
+    assert_eq!(
+        decrypted,
+        &b"The quick brown fox jumps over the lazy dogs and cats"[..]
+    );
+
+    assert!(edit(&encrypted, key, 44, b"!").is_err());
+}
+
+#[test]
+fn test_recover_with_edit() {
+    let service = EditService::new();
+    let plaintext = b"Some secret plaintext, longer than a block or two".to_vec();
+    let encrypted = service.encrypt(&plaintext).unwrap();
+    let recovered = recover_with_edit(
+        |ct, offset, text| service.edit(ct, offset, text),
+        &encrypted,
+    )
+    .unwrap();
+    assert_eq!(recovered, plaintext);
+}
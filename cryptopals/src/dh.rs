@@ -0,0 +1,117 @@
+use crate::aes::BLOCK_SIZE;
+use crate::biguint::BigUint;
+use crate::merkle_damgard::MerkleDamgardHash;
+
+/// The 1536-bit MODP prime from RFC 3526, used with generator 2
+///
+pub const NIST_PRIME: &str = "
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a0879
+    8e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0b
+    ff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361c55
+    d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb9ed529077096966d670c354e4abc9804f1746c
+    08ca237327ffffffffffffffff";
+
+pub const NIST_GENERATOR: u64 = 2;
+
+/// A Diffie-Hellman group, the prime modulus p and generator g
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DhGroup {
+    pub p: BigUint,
+    pub g: BigUint,
+}
+
+impl DhGroup {
+    pub fn new(p: BigUint, g: BigUint) -> DhGroup {
+        DhGroup { p, g }
+    }
+
+    pub fn nist() -> DhGroup {
+        DhGroup {
+            p: BigUint::from_hex(NIST_PRIME).unwrap(),
+            g: BigUint::from_u64(NIST_GENERATOR),
+        }
+    }
+
+    /// A random private exponent in [1, p) and its public key g^a mod p
+    ///
+    pub fn generate_keypair(&self) -> KeyPair {
+        self.keypair(BigUint::random_range(&BigUint::one(), &self.p))
+    }
+
+    /// The key pair for a chosen private exponent
+    ///
+    pub fn keypair(&self, private: BigUint) -> KeyPair {
+        let public = self.g.modpow(&private, &self.p);
+        KeyPair { private, public }
+    }
+}
+
+/// One side's half of a key exchange
+///
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    private: BigUint,
+    public: BigUint,
+}
+
+impl KeyPair {
+    pub fn public(&self) -> &BigUint {
+        &self.public
+    }
+
+    /// The secret shared with whoever owns the other public key, other^private mod p
+    ///
+    pub fn shared_secret(&self, group: &DhGroup, other_public: &BigUint) -> BigUint {
+        other_public.modpow(&self.private, &group.p)
+    }
+}
+
+/// An AES-128 key from the shared secret, the first 16 bytes of the hash of its big-endian
+/// bytes
+///
+pub fn derive_key<H: MerkleDamgardHash>(secret: &BigUint) -> Vec<u8> {
+    let mut key = H::digest(&secret.to_bytes_be());
+    key.truncate(BLOCK_SIZE);
+    key
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::{hex_converter::hex_encode, sha1::Sha1, sha256::Sha256};
+
+#[test]
+fn test_dh_small_group() {
+    let group = DhGroup::new(BigUint::from_u64(37), BigUint::from_u64(5));
+    for _ in 0..20 {
+        let a = group.generate_keypair();
+        let b = group.generate_keypair();
+        assert!(*a.public() < group.p);
+        assert_eq!(
+            a.shared_secret(&group, b.public()),
+            b.shared_secret(&group, a.public())
+        );
+    }
+}
+
+#[test]
+fn test_dh_nist_group() {
+    let group = DhGroup::nist();
+    assert_eq!(group.p.bits(), 1536);
+    let a = group.generate_keypair();
+    let b = group.generate_keypair();
+    let s = a.shared_secret(&group, b.public());
+    assert_eq!(s, b.shared_secret(&group, a.public()));
+    assert_eq!(derive_key::<Sha1>(&s), derive_key::<Sha1>(&s));
+    assert_eq!(derive_key::<Sha256>(&s).len(), BLOCK_SIZE);
+}
+
+#[test]
+fn test_dh_derive_key() {
+    //A zero secret is hashed as a single zero byte
+    assert_eq!(
+        hex_encode(&derive_key::<Sha1>(&BigUint::zero())),
+        "5BA93C9DB0CFF93F52B521D7420E43F6"
+    );
+}
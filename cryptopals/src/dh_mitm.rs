@@ -0,0 +1,438 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::biguint::BigUint;
+use crate::dh::{derive_key, DhGroup, KeyPair};
+use crate::merkle_damgard::MerkleDamgardHash;
+use crate::modes::{cbc_decrypt, cbc_encrypt, ModeError};
+use crate::random::random_bytes;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ProtocolError {
+    UnexpectedMessage,
+    EchoMismatch,
+    Mode(ModeError),
+}
+
+impl Error for ProtocolError {
+    fn description(&self) -> &str {
+        match *self {
+            ProtocolError::UnexpectedMessage => "Message is out of sequence",
+            ProtocolError::EchoMismatch => "Echoed message does not match what was sent",
+            ProtocolError::Mode(_) => "Message could not be decrypted",
+        }
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProtocolError::UnexpectedMessage => write!(f, "Message is out of sequence"),
+            ProtocolError::EchoMismatch => write!(f, "Echoed message does not match what was sent"),
+            ProtocolError::Mode(e) => write!(f, "Message could not be decrypted: {}", e),
+        }
+    }
+}
+
+impl From<ModeError> for ProtocolError {
+    fn from(e: ModeError) -> Self {
+        ProtocolError::Mode(e)
+    }
+}
+
+/// What the actors send each other. The group is negotiated and acknowledged, then the
+/// public keys are swapped, then a message is encrypted with the derived key and echoed.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Negotiate(DhGroup),
+    Ack(DhGroup),
+    PublicKey(BigUint),
+    /// AES-128-CBC ciphertext followed by its random IV
+    Encrypted(Vec<u8>),
+}
+
+/// Encrypt under a fresh random IV, which is appended to the ciphertext
+///
+pub fn encrypt_message(key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let cipher = Aes::new(key).unwrap();
+    let iv = random_bytes(BLOCK_SIZE);
+    let mut data = cbc_encrypt(&cipher, &iv, plaintext).unwrap();
+    data.extend(iv);
+    data
+}
+
+pub fn decrypt_message(key: &[u8], data: &[u8]) -> std::result::Result<Vec<u8>, ModeError> {
+    if data.len() < BLOCK_SIZE {
+        return Err(ModeError::InvalidIvLength);
+    }
+    let cipher = Aes::new(key).unwrap();
+    let (ciphertext, iv) = data.split_at(data.len() - BLOCK_SIZE);
+    cbc_decrypt(&cipher, iv, ciphertext)
+}
+
+/// Actor A, who picks the group and sends a message it expects to have echoed back
+///
+pub struct Initiator<H> {
+    group: DhGroup,
+    message: Vec<u8>,
+    keypair: Option<KeyPair>,
+    key: Option<Vec<u8>>,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgardHash> Initiator<H> {
+    pub fn new(group: DhGroup, message: &[u8]) -> Initiator<H> {
+        Initiator {
+            group,
+            message: message.to_vec(),
+            keypair: None,
+            key: None,
+            hash: PhantomData,
+        }
+    }
+
+    pub fn start(&self) -> Message {
+        Message::Negotiate(self.group.clone())
+    }
+
+    /// Handle B's reply, returning what to send next or None once the echo has been checked
+    ///
+    pub fn receive(
+        &mut self,
+        message: Message,
+    ) -> std::result::Result<Option<Message>, ProtocolError> {
+        match (message, &self.keypair, &self.key) {
+            (Message::Ack(group), None, None) => {
+                self.group = group;
+                let keypair = self.group.generate_keypair();
+                let public = keypair.public().clone();
+                self.keypair = Some(keypair);
+                Ok(Some(Message::PublicKey(public)))
+            }
+            (Message::PublicKey(other), Some(keypair), None) => {
+                let key = derive_key::<H>(&keypair.shared_secret(&self.group, &other));
+                let encrypted = encrypt_message(&key, &self.message);
+                self.key = Some(key);
+                Ok(Some(Message::Encrypted(encrypted)))
+            }
+            (Message::Encrypted(data), Some(_), Some(key)) => {
+                if decrypt_message(key, &data)? != self.message {
+                    return Err(ProtocolError::EchoMismatch);
+                }
+                Ok(None)
+            }
+            _ => Err(ProtocolError::UnexpectedMessage),
+        }
+    }
+}
+
+/// Actor B, who accepts whatever group it is offered and echoes messages back re-encrypted
+/// under its own IV
+///
+pub struct Responder<H> {
+    group: Option<DhGroup>,
+    key: Option<Vec<u8>>,
+    received: Vec<Vec<u8>>,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgardHash> Responder<H> {
+    pub fn new() -> Responder<H> {
+        Responder {
+            group: None,
+            key: None,
+            received: Vec::new(),
+            hash: PhantomData,
+        }
+    }
+
+    /// The plaintexts B has decrypted
+    ///
+    pub fn received(&self) -> &[Vec<u8>] {
+        &self.received
+    }
+
+    pub fn receive(&mut self, message: Message) -> std::result::Result<Message, ProtocolError> {
+        match (message, &self.group, &self.key) {
+            (Message::Negotiate(group), None, None) => {
+                self.group = Some(group.clone());
+                Ok(Message::Ack(group))
+            }
+            (Message::PublicKey(other), Some(group), None) => {
+                let keypair = group.generate_keypair();
+                self.key = Some(derive_key::<H>(&keypair.shared_secret(group, &other)));
+                Ok(Message::PublicKey(keypair.public().clone()))
+            }
+            (Message::Encrypted(data), Some(_), Some(key)) => {
+                let plaintext = decrypt_message(key, &data)?;
+                let echo = encrypt_message(key, &plaintext);
+                self.received.push(plaintext);
+                Ok(Message::Encrypted(echo))
+            }
+            _ => Err(ProtocolError::UnexpectedMessage),
+        }
+    }
+}
+
+impl<H: MerkleDamgardHash> Default for Responder<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The link between A and B, which may be tampered with in either direction
+///
+pub trait Channel {
+    fn to_responder(&mut self, message: Message) -> Message;
+
+    fn to_initiator(&mut self, message: Message) -> Message;
+}
+
+/// A channel that delivers messages untouched
+///
+pub struct Wire;
+
+impl Channel for Wire {
+    fn to_responder(&mut self, message: Message) -> Message {
+        message
+    }
+
+    fn to_initiator(&mut self, message: Message) -> Message {
+        message
+    }
+}
+
+/// Run the protocol from A's first message until A has checked the echo
+///
+pub fn run_echo<H: MerkleDamgardHash, C: Channel>(
+    initiator: &mut Initiator<H>,
+    responder: &mut Responder<H>,
+    channel: &mut C,
+) -> std::result::Result<(), ProtocolError> {
+    let mut message = initiator.start();
+    loop {
+        let reply = responder.receive(channel.to_responder(message))?;
+        match initiator.receive(channel.to_initiator(reply))? {
+            Some(next) => message = next,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Replaces both public keys with p, so each side computes p^x mod p = 0 as the secret and
+/// the key is the hash of zero
+///
+pub struct KeyFixingMitm<H> {
+    p: Option<BigUint>,
+    recovered: Vec<Vec<u8>>,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgardHash> KeyFixingMitm<H> {
+    pub fn new() -> KeyFixingMitm<H> {
+        KeyFixingMitm {
+            p: None,
+            recovered: Vec::new(),
+            hash: PhantomData,
+        }
+    }
+
+    /// The plaintexts of the encrypted messages seen in either direction
+    ///
+    pub fn recovered(&self) -> &[Vec<u8>] {
+        &self.recovered
+    }
+
+    fn intercept(&mut self, message: Message) -> Message {
+        match message {
+            Message::Negotiate(ref group) | Message::Ack(ref group) => {
+                self.p = Some(group.p.clone());
+                message
+            }
+            Message::PublicKey(public) => Message::PublicKey(self.p.clone().unwrap_or(public)),
+            Message::Encrypted(data) => {
+                let key = derive_key::<H>(&BigUint::zero());
+                if let Ok(plaintext) = decrypt_message(&key, &data) {
+                    self.recovered.push(plaintext);
+                }
+                Message::Encrypted(data)
+            }
+        }
+    }
+}
+
+impl<H: MerkleDamgardHash> Default for KeyFixingMitm<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: MerkleDamgardHash> Channel for KeyFixingMitm<H> {
+    fn to_responder(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+
+    fn to_initiator(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+}
+
+/// Generators that collapse the shared secret to values that can be predicted
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MaliciousGenerator {
+    /// Every power of 1 is 1
+    One,
+    /// Every power of p is 0 mod p
+    P,
+    /// Powers of p - 1 are 1 or p - 1 depending on whether the exponent is even
+    PMinusOne,
+}
+
+impl MaliciousGenerator {
+    pub fn value(self, p: &BigUint) -> BigUint {
+        match self {
+            MaliciousGenerator::One => BigUint::one(),
+            MaliciousGenerator::P => p.clone(),
+            MaliciousGenerator::PMinusOne => p - &BigUint::one(),
+        }
+    }
+}
+
+/// Swaps the generator in the negotiation and its acknowledgement, so both sides agree on a
+/// group whose shared secret the MITM can work out from the public keys alone
+///
+pub struct GeneratorMitm<H> {
+    generator: MaliciousGenerator,
+    p: Option<BigUint>,
+    public_keys: Vec<BigUint>,
+    recovered: Vec<Vec<u8>>,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgardHash> GeneratorMitm<H> {
+    pub fn new(generator: MaliciousGenerator) -> GeneratorMitm<H> {
+        GeneratorMitm {
+            generator,
+            p: None,
+            public_keys: Vec::new(),
+            recovered: Vec::new(),
+            hash: PhantomData,
+        }
+    }
+
+    /// The plaintexts of the encrypted messages seen in either direction
+    ///
+    pub fn recovered(&self) -> &[Vec<u8>] {
+        &self.recovered
+    }
+
+    //With g = p - 1 the secret (p - 1)^ab is p - 1 only if a and b are both odd, which is
+    //when both public keys are p - 1 themselves
+    fn shared_secret(&self, p: &BigUint) -> BigUint {
+        match self.generator {
+            MaliciousGenerator::One => BigUint::one(),
+            MaliciousGenerator::P => BigUint::zero(),
+            MaliciousGenerator::PMinusOne => {
+                let p_minus_one = p - &BigUint::one();
+                if self.public_keys.iter().all(|public| *public == p_minus_one) {
+                    p_minus_one
+                } else {
+                    BigUint::one()
+                }
+            }
+        }
+    }
+
+    fn intercept(&mut self, message: Message) -> Message {
+        match message {
+            Message::Negotiate(group) => {
+                let g = self.generator.value(&group.p);
+                self.p = Some(group.p.clone());
+                Message::Negotiate(DhGroup::new(group.p, g))
+            }
+            Message::Ack(group) => {
+                let g = self.generator.value(&group.p);
+                Message::Ack(DhGroup::new(group.p, g))
+            }
+            Message::PublicKey(public) => {
+                self.public_keys.push(public.clone());
+                Message::PublicKey(public)
+            }
+            Message::Encrypted(data) => {
+                if let Some(p) = &self.p {
+                    let key = derive_key::<H>(&self.shared_secret(p));
+                    if let Ok(plaintext) = decrypt_message(&key, &data) {
+                        self.recovered.push(plaintext);
+                    }
+                }
+                Message::Encrypted(data)
+            }
+        }
+    }
+}
+
+impl<H: MerkleDamgardHash> Channel for GeneratorMitm<H> {
+    fn to_responder(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+
+    fn to_initiator(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::{sha1::Sha1, sha256::Sha256};
+
+#[cfg(test)]
+const MESSAGE: &[u8] = b"Attack at dawn";
+
+#[test]
+fn test_dh_mitm_echo() {
+    let mut a = Initiator::<Sha256>::new(DhGroup::nist(), MESSAGE);
+    let mut b = Responder::new();
+    run_echo(&mut a, &mut b, &mut Wire).unwrap();
+    assert_eq!(b.received(), &[MESSAGE.to_vec()]);
+}
+
+#[test]
+fn test_dh_mitm_out_of_sequence() {
+    let mut b = Responder::<Sha1>::new();
+    assert_eq!(
+        b.receive(Message::PublicKey(BigUint::one())),
+        Err(ProtocolError::UnexpectedMessage)
+    );
+}
+
+#[test]
+fn test_dh_mitm_key_fixing() {
+    let mut a = Initiator::<Sha1>::new(DhGroup::nist(), MESSAGE);
+    let mut b = Responder::new();
+    let mut mitm = KeyFixingMitm::<Sha1>::new();
+    run_echo(&mut a, &mut b, &mut mitm).unwrap();
+    assert_eq!(mitm.recovered(), &[MESSAGE.to_vec(), MESSAGE.to_vec()]);
+}
+
+#[test]
+fn test_dh_mitm_malicious_generators() {
+    //A small group so that both parities of p - 1 exponents turn up quickly
+    let group = DhGroup::new(BigUint::from_u64(4_294_967_291), BigUint::from_u64(2));
+    for &generator in &[
+        MaliciousGenerator::One,
+        MaliciousGenerator::P,
+        MaliciousGenerator::PMinusOne,
+    ] {
+        for _ in 0..8 {
+            let mut a = Initiator::<Sha1>::new(group.clone(), MESSAGE);
+            let mut b = Responder::new();
+            let mut mitm = GeneratorMitm::<Sha1>::new(generator);
+            run_echo(&mut a, &mut b, &mut mitm).unwrap();
+            assert_eq!(mitm.recovered(), &[MESSAGE.to_vec(), MESSAGE.to_vec()]);
+        }
+    }
+}
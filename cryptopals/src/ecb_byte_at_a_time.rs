@@ -0,0 +1,191 @@
+use crate::ecb_detector::detect_ecb;
+use crate::error::{CryptoError, Result};
+
+//Largest block size we will look for
+const MAX_BLOCK_SIZE: usize = 64;
+
+/// What has been discovered about an ECB oracle that encrypts prefix || input || secret
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OracleProfile {
+    pub block_size: usize,
+    pub prefix_len: usize,
+    pub secret_len: usize,
+}
+
+/// Recover the secret that an ECB encryption oracle appends to our input, one byte at a time.
+/// The oracle may also prepend a fixed prefix that we don't control. The block size, prefix
+/// length and secret length are all discovered from the oracle.
+///
+pub fn byte_at_a_time_decrypt<F: Fn(&[u8]) -> Vec<u8>>(oracle: F) -> Result<Vec<u8>> {
+    let profile = profile_oracle(&oracle)?;
+    let bs = profile.block_size;
+
+    //Filler that completes the prefix's last block so our input starts on a block boundary
+    let align = (bs - profile.prefix_len % bs) % bs;
+    let first_block = (profile.prefix_len + align) / bs;
+
+    let mut recovered: Vec<u8> = Vec::with_capacity(profile.secret_len);
+    for i in 0..profile.secret_len {
+        //Push the secret along so that the unknown byte is the last byte of a block
+        let shift = bs - 1 - (i % bs);
+        let target_block = first_block + i / bs;
+        let mut input = vec![b'A'; align + shift];
+        let encrypted = oracle(&input);
+        let target = block(&encrypted, bs, target_block)?.to_vec();
+
+        //Every possible last byte after the previous bs-1 bytes of (filler || recovered),
+        //all 256 candidates are encrypted in a single query
+        let mut window: Vec<u8> = vec![b'A'; bs - 1];
+        window.extend(&recovered);
+        let window = &window[window.len() - (bs - 1)..];
+
+        input.truncate(align);
+        for c in 0..=255u8 {
+            input.extend(window);
+            input.push(c);
+        }
+        let dictionary = oracle(&input);
+
+        let found = (0..256)
+            .find(|&c| block(&dictionary, bs, first_block + c).ok() == Some(&target[..]))
+            .ok_or(CryptoError::NoSolution("no byte matches the target block"))?;
+        recovered.push(found as u8);
+    }
+
+    Ok(recovered)
+}
+
+/// Discover the block size, prefix length and secret length of the oracle
+///
+pub fn profile_oracle<F: Fn(&[u8]) -> Vec<u8>>(oracle: &F) -> Result<OracleProfile> {
+    let block_size = discover_block_size(oracle)?;
+
+    if !detect_ecb(&oracle(&vec![b'A'; 3 * block_size]), block_size).is_ecb() {
+        return Err(CryptoError::NoSolution("oracle is not using ECB"));
+    }
+
+    let prefix_len = discover_prefix_len(oracle, block_size)?;
+    let secret_len = discover_secret_len(oracle, block_size, prefix_len)?;
+    Ok(OracleProfile {
+        block_size,
+        prefix_len,
+        secret_len,
+    })
+}
+
+/// The ciphertext grows a whole block at a time as the input grows so the size of the first
+/// jump in length is the block size
+///
+pub fn discover_block_size<F: Fn(&[u8]) -> Vec<u8>>(oracle: &F) -> Result<usize> {
+    let initial_len = oracle(&[]).len();
+    (1..=MAX_BLOCK_SIZE)
+        .map(|n| oracle(&vec![b'A'; n]).len())
+        .find(|&len| len != initial_len)
+        .map(|len| len - initial_len)
+        .ok_or(CryptoError::NoSolution("ciphertext length never changed"))
+}
+
+/// Grow a run of identical bytes until two identical ciphertext blocks appear. At that point
+/// the run has filled the prefix's last block and the two blocks after it.
+/// This is done with two different fill bytes in case the prefix happens to end with one of them.
+///
+pub fn discover_prefix_len<F: Fn(&[u8]) -> Vec<u8>>(
+    oracle: &F,
+    block_size: usize,
+) -> Result<usize> {
+    let find_with = |fill: u8| -> Result<usize> {
+        for extra in 0..block_size {
+            let encrypted = oracle(&vec![fill; 2 * block_size + extra]);
+            let blocks: Vec<&[u8]> = encrypted.chunks(block_size).collect();
+            if let Some(idx) = blocks.windows(2).position(|w| w[0] == w[1]) {
+                return Ok(idx * block_size - extra);
+            }
+        }
+        Err(CryptoError::NoSolution("could not align input to a block"))
+    };
+
+    Ok(find_with(b'A')?.max(find_with(b'B')?))
+}
+
+/// With the input aligned to a block, add bytes until the (PKCS#7 padded) ciphertext grows.
+/// At that point prefix + input + secret exactly fills the original ciphertext length.
+///
+pub fn discover_secret_len<F: Fn(&[u8]) -> Vec<u8>>(
+    oracle: &F,
+    block_size: usize,
+    prefix_len: usize,
+) -> Result<usize> {
+    let align = (block_size - prefix_len % block_size) % block_size;
+    let initial_len = oracle(&vec![b'A'; align]).len();
+    (1..=block_size)
+        .find(|&n| oracle(&vec![b'A'; align + n]).len() > initial_len)
+        .map(|n| initial_len - prefix_len - align - n)
+        .ok_or(CryptoError::NoSolution("ciphertext length never changed"))
+}
+
+fn block(data: &[u8], block_size: usize, idx: usize) -> Result<&[u8]> {
+    data.get(idx * block_size..(idx + 1) * block_size)
+        .ok_or(CryptoError::NoSolution(
+            "ciphertext is shorter than expected",
+        ))
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::aes::Aes;
+#[cfg(test)]
+use crate::modes::{cbc_encrypt, ecb_encrypt};
+
+#[cfg(test)]
+const TEST_SECRET: &[u8] = b"Rollin' in my 5.0\nWith my rag-top down so my hair can blow";
+
+#[cfg(test)]
+fn test_oracle(prefix: Vec<u8>) -> impl Fn(&[u8]) -> Vec<u8> {
+    let cipher = Aes::new(&crate::random::random_bytes(16)).unwrap();
+    move |input| {
+        let mut plaintext = prefix.clone();
+        plaintext.extend(input);
+        plaintext.extend(TEST_SECRET);
+        ecb_encrypt(&cipher, &plaintext).unwrap()
+    }
+}
+
+#[test]
+fn test_ecb_byte_at_a_time_no_prefix() {
+    let oracle = test_oracle(Vec::new());
+    let profile = profile_oracle(&oracle).unwrap();
+    assert_eq!(
+        profile,
+        OracleProfile {
+            block_size: 16,
+            prefix_len: 0,
+            secret_len: TEST_SECRET.len()
+        }
+    );
+    assert_eq!(byte_at_a_time_decrypt(oracle).unwrap(), TEST_SECRET);
+}
+
+#[test]
+fn test_ecb_byte_at_a_time_prefix_lengths() {
+    for &prefix_len in [1, 5, 15, 16, 17, 31, 33].iter() {
+        let oracle = test_oracle(crate::random::random_bytes(prefix_len));
+        assert_eq!(profile_oracle(&oracle).unwrap().prefix_len, prefix_len);
+        assert_eq!(byte_at_a_time_decrypt(oracle).unwrap(), TEST_SECRET);
+    }
+}
+
+#[test]
+fn test_ecb_byte_at_a_time_prefix_ends_with_fill() {
+    let oracle = test_oracle(b"xyzA".to_vec());
+    assert_eq!(profile_oracle(&oracle).unwrap().prefix_len, 4);
+    assert_eq!(byte_at_a_time_decrypt(oracle).unwrap(), TEST_SECRET);
+}
+
+#[test]
+fn test_ecb_byte_at_a_time_rejects_cbc() {
+    let cipher = Aes::new(b"YELLOW SUBMARINE").unwrap();
+    let oracle = |input: &[u8]| cbc_encrypt(&cipher, &[0; 16], input).unwrap();
+    assert!(byte_at_a_time_decrypt(oracle).is_err());
+}
@@ -0,0 +1,199 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::modes::{ecb_decrypt, ecb_encrypt};
+use crate::padding::pkcs7_pad;
+use crate::random::random_bytes;
+use std::error::Error;
+use std::fmt;
+
+//What profile_for puts either side of the email
+const EMAIL_PREFIX: &str = "email=";
+const EMAIL_SUFFIX: &str = "&uid=10&role=";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CookieError {
+    ReservedChar,
+    MalformedPair,
+}
+
+impl Error for CookieError {
+    fn description(&self) -> &str {
+        match *self {
+            CookieError::ReservedChar => "Cookie values cannot contain '&' or '='",
+            CookieError::MalformedPair => "Cookie pairs must be of the form key=value",
+        }
+    }
+}
+
+impl fmt::Display for CookieError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CookieError::ReservedChar => write!(f, "Cookie values cannot contain '&' or '='"),
+            CookieError::MalformedPair => write!(f, "Cookie pairs must be of the form key=value"),
+        }
+    }
+}
+
+/// Parse a k=v&k=v cookie into its key value pairs, preserving order
+///
+pub fn parse_cookie(cookie: &str) -> std::result::Result<Vec<(String, String)>, CookieError> {
+    cookie
+        .split('&')
+        .map(|pair| {
+            let mut kv = pair.split('=');
+            match (kv.next(), kv.next(), kv.next()) {
+                (Some(k), Some(v), None) if !k.is_empty() => Ok((k.to_string(), v.to_string())),
+                _ => Err(CookieError::MalformedPair),
+            }
+        })
+        .collect()
+}
+
+/// Encode key value pairs as a k=v&k=v cookie. The metacharacters can't be escaped so
+/// keys and values containing them are rejected.
+///
+pub fn encode_cookie(pairs: &[(&str, &str)]) -> std::result::Result<String, CookieError> {
+    let has_reserved = |s: &str| s.contains('&') || s.contains('=');
+    if pairs
+        .iter()
+        .any(|(k, v)| has_reserved(k) || has_reserved(v))
+    {
+        return Err(CookieError::ReservedChar);
+    }
+
+    Ok(pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&"))
+}
+
+/// Encode a user profile for the given email
+///
+pub fn profile_for(email: &str) -> std::result::Result<String, CookieError> {
+    encode_cookie(&[("email", email), ("uid", "10"), ("role", "user")])
+}
+
+/// Hands out ECB encrypted profiles and reads them back, under a key only it knows
+///
+pub struct ProfileService {
+    cipher: Aes,
+}
+
+impl ProfileService {
+    pub fn new() -> ProfileService {
+        ProfileService {
+            cipher: Aes::new(&random_bytes(BLOCK_SIZE)).unwrap(),
+        }
+    }
+
+    /// The encrypted profile for the given email
+    ///
+    pub fn encrypted_profile_for(&self, email: &str) -> Result<Vec<u8>> {
+        Ok(ecb_encrypt(&self.cipher, profile_for(email)?.as_bytes())?)
+    }
+
+    /// Decrypt and parse an encrypted profile
+    ///
+    pub fn decrypt_profile(&self, encrypted: &[u8]) -> Result<Vec<(String, String)>> {
+        let decrypted = String::from_utf8(ecb_decrypt(&self.cipher, encrypted)?)?;
+        Ok(parse_cookie(&decrypted)?)
+    }
+}
+
+impl Default for ProfileService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forge an encrypted role=admin profile using only the ability to request profiles.
+///
+/// Choosing the email length so that "role=" ends a block lets us take those blocks and
+/// paste on a block that decrypts to "admin" plus valid padding. That block comes from an
+/// email that pushes "admin\x0b\x0b..." to the start of a block.
+///
+pub fn forge_admin_profile<F: Fn(&str) -> Result<Vec<u8>>>(
+    encrypted_profile_for: F,
+) -> Result<Vec<u8>> {
+    //Pad "email=" out to a block so the admin block starts on a boundary
+    let align = BLOCK_SIZE - EMAIL_PREFIX.len() % BLOCK_SIZE;
+    let admin_block = String::from_utf8(pkcs7_pad(b"admin", BLOCK_SIZE))?;
+    let email = "A".repeat(align) + &admin_block;
+    let encrypted = encrypted_profile_for(&email)?;
+    let admin_start = EMAIL_PREFIX.len() + align;
+    let admin_encrypted = encrypted
+        .get(admin_start..admin_start + BLOCK_SIZE)
+        .ok_or(CryptoError::NoSolution("profile is shorter than expected"))?;
+
+    //Email of the length that makes "email=...&uid=10&role=" end on a block boundary
+    let fixed_len = EMAIL_PREFIX.len() + EMAIL_SUFFIX.len();
+    let user = "admin@bar.com";
+    let extra = (BLOCK_SIZE - (fixed_len + user.len()) % BLOCK_SIZE) % BLOCK_SIZE;
+    let email = "a".repeat(extra) + user;
+    let head_len = fixed_len + email.len();
+    let encrypted = encrypted_profile_for(&email)?;
+
+    let mut forged = encrypted
+        .get(..head_len)
+        .ok_or(CryptoError::NoSolution("profile is shorter than expected"))?
+        .to_vec();
+    forged.extend(admin_encrypted);
+    Ok(forged)
+}
+
+/// Unit tests
+///
+#[test]
+fn test_parse_cookie() {
+    let parsed = parse_cookie("foo=bar&baz=qux&zap=zazzle").unwrap();
+    assert_eq!(
+        parsed,
+        vec![
+            ("foo".to_string(), "bar".to_string()),
+            ("baz".to_string(), "qux".to_string()),
+            ("zap".to_string(), "zazzle".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_parse_cookie_malformed() {
+    assert_eq!(parse_cookie("foo=bar&baz"), Err(CookieError::MalformedPair));
+    assert_eq!(parse_cookie("foo=bar=baz"), Err(CookieError::MalformedPair));
+    assert_eq!(parse_cookie("=bar"), Err(CookieError::MalformedPair));
+}
+
+#[test]
+fn test_profile_for() {
+    assert_eq!(
+        profile_for("foo@bar.com").unwrap(),
+        "email=foo@bar.com&uid=10&role=user"
+    );
+}
+
+#[test]
+fn test_profile_for_rejects_metacharacters() {
+    assert_eq!(
+        profile_for("foo@bar.com&role=admin"),
+        Err(CookieError::ReservedChar)
+    );
+    assert_eq!(profile_for("foo=bar"), Err(CookieError::ReservedChar));
+}
+
+#[test]
+fn test_profile_service_round_trip() {
+    let service = ProfileService::new();
+    let encrypted = service.encrypted_profile_for("foo@bar.com").unwrap();
+    let profile = service.decrypt_profile(&encrypted).unwrap();
+    assert_eq!(profile[2], ("role".to_string(), "user".to_string()));
+}
+
+#[test]
+fn test_forge_admin_profile() {
+    let service = ProfileService::new();
+    let forged = forge_admin_profile(|email| service.encrypted_profile_for(email)).unwrap();
+    let profile = service.decrypt_profile(&forged).unwrap();
+    assert_eq!(profile.len(), 3);
+    assert_eq!(profile[2], ("role".to_string(), "admin".to_string()));
+}
@@ -0,0 +1,76 @@
+/// Result of looking for repeated ciphertext blocks
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EcbDetection {
+    /// Number of blocks that are a repeat of an earlier block
+    pub repeated_blocks: usize,
+    /// Number of whole blocks examined
+    pub total_blocks: usize,
+    /// Fraction (0-1) of the blocks after the first that are repeats
+    pub confidence: f32,
+}
+
+impl EcbDetection {
+    /// Any repeated block is a strong sign of ECB as it is vanishingly unlikely in other modes
+    ///
+    pub fn is_ecb(&self) -> bool {
+        self.repeated_blocks > 0
+    }
+}
+
+/// Look for repeated blocks of the given size in the ciphertext. ECB encrypts identical
+/// plaintext blocks to identical ciphertext blocks, so repeats suggest it was ECB encrypted.
+///
+pub fn detect_ecb(ciphertext: &[u8], block_size: usize) -> EcbDetection {
+    assert!(block_size > 0, "Block size must be non-zero");
+
+    //Sort the blocks so we can count the number of repeats just by counting the run lengths
+    let mut blocks: Vec<&[u8]> = ciphertext.chunks_exact(block_size).collect();
+    blocks.sort_unstable();
+
+    let total_blocks = blocks.len();
+    let repeated_blocks = count_runs(&blocks);
+    let confidence = if total_blocks > 1 {
+        repeated_blocks as f32 / (total_blocks - 1) as f32
+    } else {
+        0.0
+    };
+
+    EcbDetection {
+        repeated_blocks,
+        total_blocks,
+        confidence,
+    }
+}
+
+/// Counts the number of entries that match the previous entry in a sorted list
+///
+fn count_runs(blocks: &[&[u8]]) -> usize {
+    blocks.windows(2).filter(|w| w[0] == w[1]).count()
+}
+
+/// Unit tests
+///
+#[test]
+fn test_detect_ecb_repeats() {
+    let detection = detect_ecb(b"YELLOW SUBMARINEabcdefghijklmnopYELLOW SUBMARINE", 16);
+    assert!(detection.is_ecb());
+    assert_eq!(detection.repeated_blocks, 1);
+    assert_eq!(detection.total_blocks, 3);
+    assert!((detection.confidence - 0.5).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_detect_ecb_no_repeats() {
+    let detection = detect_ecb(b"0123456789abcdefghijklmnopqrstuv", 16);
+    assert!(!detection.is_ecb());
+    assert_eq!(detection.confidence, 0.0);
+}
+
+#[test]
+fn test_detect_ecb_block_size() {
+    let data = b"abcdabcdabcdabcd";
+    assert_eq!(detect_ecb(data, 4).repeated_blocks, 3);
+    assert_eq!(detect_ecb(data, 8).repeated_blocks, 1);
+    assert_eq!(detect_ecb(data, 16).repeated_blocks, 0);
+}
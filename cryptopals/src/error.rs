@@ -0,0 +1,187 @@
+use crate::{
+    aes, base64_converter, dh_mitm, ecb_cut_and_paste, hamming, hex_converter, modes, padding,
+    payloads, rsa, srp,
+};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::string::FromUtf8Error;
+
+/// Crate wide error that wraps the errors raised by the primitives so solvers can use `?`
+///
+#[derive(Debug)]
+pub enum CryptoError {
+    Hex(hex_converter::DecodeError),
+    Base64(base64_converter::DecodeError),
+    Hamming(hamming::HammingError),
+    Payload(payloads::PayloadError),
+    Io(io::Error),
+    Utf8(FromUtf8Error),
+    Cipher(aes::AesError),
+    Mode(modes::ModeError),
+    Padding(padding::PaddingError),
+    Cookie(ecb_cut_and_paste::CookieError),
+    Protocol(dh_mitm::ProtocolError),
+    Srp(srp::SrpError),
+    Rsa(rsa::RsaError),
+    NoSolution(&'static str),
+}
+
+pub type Result<T> = std::result::Result<T, CryptoError>;
+
+impl Error for CryptoError {
+    fn description(&self) -> &str {
+        match *self {
+            CryptoError::Hex(_) => "Invalid hex",
+            CryptoError::Base64(_) => "Invalid base64",
+            CryptoError::Hamming(_) => "Hamming distance failed",
+            CryptoError::Payload(_) => "Payload could not be loaded",
+            CryptoError::Io(_) => "I/O failed",
+            CryptoError::Utf8(_) => "Result is not valid UTF-8",
+            CryptoError::Cipher(_) => "Cipher operation failed",
+            CryptoError::Mode(_) => "Block cipher mode failed",
+            CryptoError::Padding(_) => "Invalid padding",
+            CryptoError::Cookie(_) => "Invalid cookie",
+            CryptoError::Protocol(_) => "Protocol failed",
+            CryptoError::Srp(_) => "SRP login failed",
+            CryptoError::Rsa(_) => "RSA operation failed",
+            CryptoError::NoSolution(_) => "No solution found",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CryptoError::Hex(e) => Some(e),
+            CryptoError::Base64(e) => Some(e),
+            CryptoError::Hamming(e) => Some(e),
+            CryptoError::Payload(e) => Some(e),
+            CryptoError::Io(e) => Some(e),
+            CryptoError::Utf8(e) => Some(e),
+            CryptoError::Cipher(e) => Some(e),
+            CryptoError::Mode(e) => Some(e),
+            CryptoError::Padding(e) => Some(e),
+            CryptoError::Cookie(e) => Some(e),
+            CryptoError::Protocol(e) => Some(e),
+            CryptoError::Srp(e) => Some(e),
+            CryptoError::Rsa(e) => Some(e),
+            CryptoError::NoSolution(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CryptoError::Hex(e) => write!(f, "Invalid hex: {}", e),
+            CryptoError::Base64(e) => write!(f, "Invalid base64: {}", e),
+            CryptoError::Hamming(e) => write!(f, "Hamming distance failed: {}", e),
+            CryptoError::Payload(e) => write!(f, "{}", e),
+            CryptoError::Io(e) => write!(f, "I/O failed: {}", e),
+            CryptoError::Utf8(e) => write!(f, "Result is not valid UTF-8: {}", e),
+            CryptoError::Cipher(e) => write!(f, "Cipher operation failed: {}", e),
+            CryptoError::Mode(e) => write!(f, "Block cipher mode failed: {}", e),
+            CryptoError::Padding(e) => write!(f, "Invalid padding: {}", e),
+            CryptoError::Cookie(e) => write!(f, "Invalid cookie: {}", e),
+            CryptoError::Protocol(e) => write!(f, "Protocol failed: {}", e),
+            CryptoError::Srp(e) => write!(f, "SRP login failed: {}", e),
+            CryptoError::Rsa(e) => write!(f, "RSA operation failed: {}", e),
+            CryptoError::NoSolution(what) => write!(f, "No solution found: {}", what),
+        }
+    }
+}
+
+impl From<hex_converter::DecodeError> for CryptoError {
+    fn from(e: hex_converter::DecodeError) -> Self {
+        CryptoError::Hex(e)
+    }
+}
+
+impl From<base64_converter::DecodeError> for CryptoError {
+    fn from(e: base64_converter::DecodeError) -> Self {
+        CryptoError::Base64(e)
+    }
+}
+
+impl From<hamming::HammingError> for CryptoError {
+    fn from(e: hamming::HammingError) -> Self {
+        CryptoError::Hamming(e)
+    }
+}
+
+impl From<payloads::PayloadError> for CryptoError {
+    fn from(e: payloads::PayloadError) -> Self {
+        CryptoError::Payload(e)
+    }
+}
+
+impl From<io::Error> for CryptoError {
+    fn from(e: io::Error) -> Self {
+        CryptoError::Io(e)
+    }
+}
+
+impl From<FromUtf8Error> for CryptoError {
+    fn from(e: FromUtf8Error) -> Self {
+        CryptoError::Utf8(e)
+    }
+}
+
+impl From<aes::AesError> for CryptoError {
+    fn from(e: aes::AesError) -> Self {
+        CryptoError::Cipher(e)
+    }
+}
+
+impl From<modes::ModeError> for CryptoError {
+    fn from(e: modes::ModeError) -> Self {
+        CryptoError::Mode(e)
+    }
+}
+
+impl From<padding::PaddingError> for CryptoError {
+    fn from(e: padding::PaddingError) -> Self {
+        CryptoError::Padding(e)
+    }
+}
+
+impl From<ecb_cut_and_paste::CookieError> for CryptoError {
+    fn from(e: ecb_cut_and_paste::CookieError) -> Self {
+        CryptoError::Cookie(e)
+    }
+}
+
+impl From<dh_mitm::ProtocolError> for CryptoError {
+    fn from(e: dh_mitm::ProtocolError) -> Self {
+        CryptoError::Protocol(e)
+    }
+}
+
+impl From<srp::SrpError> for CryptoError {
+    fn from(e: srp::SrpError) -> Self {
+        CryptoError::Srp(e)
+    }
+}
+
+impl From<rsa::RsaError> for CryptoError {
+    fn from(e: rsa::RsaError) -> Self {
+        CryptoError::Rsa(e)
+    }
+}
+
+/// Unit tests
+///
+#[test]
+fn test_error_from_hex() {
+    let e: CryptoError = hex_converter::hex_decode("F").unwrap_err().into();
+    assert!(matches!(
+        e,
+        CryptoError::Hex(hex_converter::DecodeError::OddInputLength)
+    ));
+    assert!(e.source().is_some());
+}
+
+#[test]
+fn test_error_from_utf8() {
+    let e: CryptoError = String::from_utf8(vec![0xff]).unwrap_err().into();
+    assert!(e.to_string().starts_with("Result is not valid UTF-8"));
+}
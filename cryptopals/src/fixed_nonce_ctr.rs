@@ -0,0 +1,139 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::error::Result;
+use crate::modes::{ctr, Counter};
+use crate::xor_decrypter::break_columns;
+
+/// Encrypt each plaintext with AES CTR under the same key and the same (zero) nonce, so
+/// every ciphertext is xor'd with the same keystream
+///
+pub fn encrypt_with_fixed_nonce(key: &[u8], plaintexts: &[Vec<u8>]) -> Result<Vec<Vec<u8>>> {
+    let cipher = Aes::new(key)?;
+    let nonce = [0u8; BLOCK_SIZE];
+    let mut encrypted = Vec::with_capacity(plaintexts.len());
+    for p in plaintexts.iter() {
+        encrypted.push(ctr(&cipher, &nonce, Counter::LittleEndianHalf, p)?);
+    }
+    Ok(encrypted)
+}
+
+/// A keystream shared by several ciphertexts (e.g. CTR with a reused nonce), recovered a byte
+/// at a time. Bytes that could not be recovered are None.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keystream {
+    bytes: Vec<Option<u8>>,
+}
+
+impl Keystream {
+    /// Stack the ciphertexts and break each column as single character XOR. The columns past
+    /// the end of the shorter ciphertexts have fewer bytes to go on so the tail is less reliable
+    /// and usually needs correcting.
+    ///
+    pub fn recover(ciphertexts: &[&[u8]]) -> Keystream {
+        Keystream {
+            bytes: break_columns(ciphertexts),
+        }
+    }
+
+    /// As recover but truncating every ciphertext to the length of the shortest, so every
+    /// column has the same amount of ciphertext behind it
+    ///
+    pub fn recover_truncated(ciphertexts: &[&[u8]]) -> Keystream {
+        let min_len = ciphertexts.iter().map(|c| c.len()).min().unwrap_or(0);
+        let truncated: Vec<&[u8]> = ciphertexts.iter().map(|c| &c[..min_len]).collect();
+        Keystream::recover(&truncated)
+    }
+
+    /// Manual correction hook. Given a guess at some of the plaintext of one ciphertext, at the
+    /// given offset, fix the keystream bytes under it.
+    ///
+    pub fn correct(&mut self, ciphertext: &[u8], offset: usize, known_plaintext: &[u8]) {
+        let end = (offset + known_plaintext.len()).min(ciphertext.len());
+        if self.bytes.len() < end {
+            self.bytes.resize(end, None);
+        }
+
+        for i in offset..end {
+            self.bytes[i] = Some(ciphertext[i] ^ known_plaintext[i - offset]);
+        }
+    }
+
+    /// The recovered keystream bytes
+    ///
+    pub fn bytes(&self) -> &[Option<u8>] {
+        &self.bytes
+    }
+
+    /// Decrypt as much of the ciphertext as the keystream covers. Bytes without a keystream
+    /// byte are shown as '?'.
+    ///
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8> {
+        ciphertext
+            .iter()
+            .enumerate()
+            .map(|(i, c)| match self.bytes.get(i) {
+                Some(Some(k)) => c ^ k,
+                _ => b'?',
+            })
+            .collect()
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+const TEST_PLAINTEXTS: [&str; 8] = [
+    "Now that the party is jumping",
+    "With the bass kicked in and the Vega's are pumpin'",
+    "Quick to the point, to the point, no faking",
+    "Cooking MC's like a pound of bacon",
+    "Burning 'em, if you ain't quick and nimble",
+    "I go crazy when I hear a cymbal",
+    "And a high hat with a souped up tempo",
+    "I'm on a roll, it's time to go solo",
+];
+
+#[cfg(test)]
+fn test_ciphertexts() -> Vec<Vec<u8>> {
+    let plaintexts: Vec<Vec<u8>> = TEST_PLAINTEXTS
+        .iter()
+        .map(|p| p.as_bytes().to_vec())
+        .collect();
+    encrypt_with_fixed_nonce(&crate::random::random_bytes(16), &plaintexts).unwrap()
+}
+
+#[test]
+fn test_keystream_recover_truncated() {
+    let ciphertexts = test_ciphertexts();
+    let refs: Vec<&[u8]> = ciphertexts.iter().map(|c| &c[..]).collect();
+    let keystream = Keystream::recover_truncated(&refs);
+    assert_eq!(keystream.bytes().len(), 29);
+
+    //With only 8 rows a few columns are wrong, but most of the text comes through
+    let decrypted = keystream.decrypt(&ciphertexts[3][..29]);
+    let correct = decrypted
+        .iter()
+        .zip(TEST_PLAINTEXTS[3].bytes())
+        .filter(|(a, b)| **a == *b)
+        .count();
+    assert!(correct > 20);
+}
+
+#[test]
+fn test_keystream_correct() {
+    let ciphertexts = test_ciphertexts();
+    let refs: Vec<&[u8]> = ciphertexts.iter().map(|c| &c[..]).collect();
+    let mut keystream = Keystream::recover(&refs);
+
+    keystream.correct(&ciphertexts[1], 0, TEST_PLAINTEXTS[1].as_bytes());
+    for (c, p) in ciphertexts.iter().zip(TEST_PLAINTEXTS.iter()) {
+        assert_eq!(keystream.decrypt(c), p.as_bytes());
+    }
+}
+
+#[test]
+fn test_keystream_decrypt_unknown() {
+    let keystream = Keystream::recover(&[&[1, 2]]);
+    assert_eq!(keystream.decrypt(&[1, 2, 3]).len(), 3);
+    assert_eq!(keystream.decrypt(&[1, 2, 3])[2], b'?');
+}
@@ -37,7 +37,7 @@ impl fmt::Display for DecodeError {
 /// Convert hex to bytes
 ///
 pub fn hex_decode(hex: &str) -> std::result::Result<Vec<u8>, DecodeError> {
-    if hex.len() % 2 != 0 {
+    if !hex.len().is_multiple_of(2) {
         Err(DecodeError::OddInputLength)
     } else {
         let expected_len = hex.len() / 2;
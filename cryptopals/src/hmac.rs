@@ -0,0 +1,74 @@
+use crate::merkle_damgard::MerkleDamgardHash;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// HMAC (RFC 2104) over any of the hashes, H((K ^ opad) || H((K ^ ipad) || message)).
+/// Keys longer than a block are hashed first, shorter ones are padded with zeros.
+///
+pub fn hmac<H: MerkleDamgardHash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block_key = if key.len() > H::BLOCK_LEN {
+        H::digest(key)
+    } else {
+        key.to_vec()
+    };
+    block_key.resize(H::BLOCK_LEN, 0);
+
+    let mut inner = H::new();
+    inner.update(&block_key.iter().map(|k| k ^ IPAD).collect::<Vec<u8>>());
+    inner.update(message);
+
+    let mut outer = H::new();
+    outer.update(&block_key.iter().map(|k| k ^ OPAD).collect::<Vec<u8>>());
+    outer.update(&inner.finalize());
+    outer.finalize()
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::{hex_converter::hex_encode, md4::Md4, sha1::Sha1, sha256::Sha256};
+
+#[test]
+fn test_hmac_sha1_rfc_2202() {
+    assert_eq!(
+        hex_encode(&hmac::<Sha1>(&[0x0b; 20], b"Hi There")),
+        "B617318655057264E28BC0B6FB378C8EF146BE00"
+    );
+    assert_eq!(
+        hex_encode(&hmac::<Sha1>(b"Jefe", b"what do ya want for nothing?")),
+        "EFFCDF6AE5EB2FA2D27416D5F184DF9C259A7C79"
+    );
+    //Key longer than the block size
+    assert_eq!(
+        hex_encode(&hmac::<Sha1>(
+            &[0xaa; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "AA4AE5E15272D00E95705637CE8A3B55ED402112"
+    );
+}
+
+#[test]
+fn test_hmac_sha256_rfc_4231() {
+    assert_eq!(
+        hex_encode(&hmac::<Sha256>(&[0x0b; 20], b"Hi There")),
+        "B0344C61D8DB38535CA8AFCEAF0BF12B881DC200C9833DA726E9376C2E32CFF7"
+    );
+    assert_eq!(
+        hex_encode(&hmac::<Sha256>(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "60E431591EE0B67F0D8A26AACBF5B77F8E0BC6213728C5140546040F0EE37F54"
+    );
+}
+
+#[test]
+fn test_hmac_md4() {
+    //No published vectors, check it is keyed and deterministic
+    let mac = hmac::<Md4>(b"key", b"message");
+    assert_eq!(mac.len(), 16);
+    assert_eq!(mac, hmac::<Md4>(b"key", b"message"));
+    assert_ne!(mac, hmac::<Md4>(b"kez", b"message"));
+}
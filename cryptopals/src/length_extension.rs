@@ -0,0 +1,133 @@
+use crate::error::{CryptoError, Result};
+use crate::merkle_damgard::MerkleDamgardHash;
+use crate::random::{random_bytes, random_range};
+use std::marker::PhantomData;
+
+/// MAC made by hashing the key followed by the message, H(key || message)
+///
+pub fn secret_prefix_mac<H: MerkleDamgardHash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut hasher = H::new();
+    hasher.update(key);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Signs and verifies messages with a secret-prefix MAC under a key of unknown length
+///
+pub struct MacService<H: MerkleDamgardHash> {
+    key: Vec<u8>,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgardHash> MacService<H> {
+    /// A service with a random key of 1 to 32 bytes
+    ///
+    pub fn new() -> MacService<H> {
+        MacService::with_key(&random_bytes(random_range(1, 33)))
+    }
+
+    pub fn with_key(key: &[u8]) -> MacService<H> {
+        MacService {
+            key: key.to_vec(),
+            hash: PhantomData,
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        secret_prefix_mac::<H>(&self.key, message)
+    }
+
+    pub fn verify(&self, message: &[u8], mac: &[u8]) -> bool {
+        self.sign(message) == mac
+    }
+}
+
+impl<H: MerkleDamgardHash> Default for MacService<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forge a MAC for message || glue padding || extension without the key. The MAC is the hash
+/// state after the padded key and message, so hashing can resume from it. The padding depends
+/// on the key length, so each length up to max_key_len is tried until the verifier accepts.
+/// Returns the forged message and its MAC.
+///
+pub fn forge_extension<H: MerkleDamgardHash, F: Fn(&[u8], &[u8]) -> bool>(
+    verify: F,
+    message: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    max_key_len: usize,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    if mac.len() != H::DIGEST_LEN {
+        return Err(CryptoError::NoSolution("MAC is not a digest of this hash"));
+    }
+
+    for key_len in 0..=max_key_len {
+        let original_len = (key_len + message.len()) as u64;
+        let glue = H::padding(original_len);
+
+        let mut hasher = H::resume(mac, original_len + glue.len() as u64);
+        hasher.update(extension);
+        let forged_mac = hasher.finalize();
+
+        let mut forged = message.to_vec();
+        forged.extend(&glue);
+        forged.extend(extension);
+        if verify(&forged, &forged_mac) {
+            return Ok((forged, forged_mac));
+        }
+    }
+    Err(CryptoError::NoSolution(
+        "no key length produced an accepted MAC",
+    ))
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::{md4::Md4, sha1::Sha1, sha256::Sha256};
+
+#[cfg(test)]
+fn check_forge_extension<H: MerkleDamgardHash>() {
+    let service = MacService::<H>::new();
+    let message = b"user=alice;role=user";
+    let mac = service.sign(message);
+
+    let (forged, forged_mac) = forge_extension::<H, _>(
+        |m, mac| service.verify(m, mac),
+        message,
+        &mac,
+        b";role=admin",
+        32,
+    )
+    .unwrap();
+    assert!(forged.starts_with(message));
+    assert!(forged.ends_with(b";role=admin"));
+    assert!(service.verify(&forged, &forged_mac));
+}
+
+#[test]
+fn test_secret_prefix_mac() {
+    let mac = secret_prefix_mac::<Sha1>(b"key", b"message");
+    assert_eq!(mac, crate::sha1::sha1(b"keymessage"));
+    assert_ne!(mac, secret_prefix_mac::<Sha1>(b"key", b"messagf"));
+    assert_ne!(mac, secret_prefix_mac::<Sha1>(b"kez", b"message"));
+}
+
+#[test]
+fn test_forge_extension() {
+    check_forge_extension::<Md4>();
+    check_forge_extension::<Sha1>();
+    check_forge_extension::<Sha256>();
+}
+
+#[test]
+fn test_forge_extension_key_too_long() {
+    let key = [0u8; 40];
+    let mac = secret_prefix_mac::<Sha1>(&key, b"message");
+    let verify = |m: &[u8], mac: &[u8]| secret_prefix_mac::<Sha1>(&key, m) == mac;
+    assert!(forge_extension::<Sha1, _>(verify, b"message", &mac, b"!", 32).is_err());
+    assert!(forge_extension::<Md4, _>(verify, b"message", &mac, b"!", 32).is_err());
+}
@@ -0,0 +1,48 @@
+//! Cryptopals challenges https://cryptopals.com
+//!
+//! The primitives (encoders, xor, hamming, padding) and the attack modules are exposed
+//! so they can be reused outside of the solver binary.
+
+pub mod aes;
+pub mod base64_converter;
+pub mod biguint;
+pub mod bitflipping;
+pub mod block_cipher;
+pub mod cbc_key_as_iv;
+pub mod challenges;
+pub mod ctr_edit;
+pub mod dh;
+pub mod dh_mitm;
+pub mod ecb_byte_at_a_time;
+pub mod ecb_cut_and_paste;
+pub mod ecb_detector;
+pub mod error;
+pub mod fixed_nonce_ctr;
+pub mod hamming;
+pub mod hex_converter;
+pub mod hmac;
+pub mod length_extension;
+pub mod md4;
+pub mod merkle_damgard;
+pub mod modes;
+pub mod mt19937;
+pub mod mt_stream_cipher;
+pub mod padding;
+pub mod padding_oracle;
+pub mod payloads;
+pub mod prime;
+pub mod random;
+pub mod rsa;
+pub mod rsa_broadcast;
+pub mod set1;
+pub mod set2;
+pub mod set3;
+pub mod set4;
+pub mod set5;
+pub mod sha1;
+pub mod sha256;
+pub mod simplified_srp;
+pub mod srp;
+pub mod timing_leak;
+pub mod xor_decrypter;
+pub mod xor_encrypter;
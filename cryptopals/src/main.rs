@@ -1,29 +1,161 @@
-#![feature(iter_map_while)]
+use cryptopals::challenges::{self, Challenge};
+use cryptopals::payloads;
+use cryptopals::set5::challenge38;
+use std::env;
+use std::process;
+use std::time::Instant;
 
-mod base64_converter;
-mod hamming;
-mod hex_converter;
-mod padding;
-mod set1;
-mod set2;
-mod xor_decrypter;
-mod xor_encrypter;
+const USAGE: &str =
+    "Usage: cryptopals [--list] [--verify] [--set <n>] [--challenge <n>] [--payloads <dir>]
+                  [--wordlist <file>]
+
+    --list              List the available challenges
+    --verify            Check each answer against the known answer
+    --set <n>           Only run the challenges in set n
+    --challenge <n>     Only run challenge n
+    --payloads <dir>    Read payload files from dir (also settable with CRYPTOPALS_PAYLOADS)
+    --wordlist <file>   Crack the password in challenge 38 with the words in file, one per line";
+
+/// What the command line has asked us to do
+///
+enum Command {
+    List,
+    Run(Vec<&'static Challenge>),
+    Verify(Vec<&'static Challenge>),
+}
 
 /// Cryptopals challenges https://cryptopals.com
 ///
 fn main() {
-    println!("=== Set 1");
-    println!("\t{}", set1::challenge1::solve());
-    println!("\t{}", set1::challenge2::solve());
-    println!("\t{}", set1::challenge3::solve());
-    println!("\t{}", set1::challenge4::solve().replace('\n', ""));
-    println!("\t{}", set1::challenge5::solve());
-    println!("\t{}", set1::challenge6::solve().replace('\n', ""));
-    println!("\t{}", set1::challenge7::solve().replace('\n', ""));
-    println!("\t{}", set1::challenge8::solve());
-
-    println!("=== Set 2");
-    println!("\t{}", set2::challenge9::solve());
+    match parse_args(env::args().skip(1)) {
+        Ok(Command::List) => list(),
+        Ok(Command::Run(selected)) => {
+            if !run(&selected) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Verify(selected)) => {
+            if !verify(&selected) {
+                process::exit(1);
+            }
+        }
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Parse the command line into the command to run
+///
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut set = None;
+    let mut number = None;
+    let mut verify = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => return Ok(Command::List),
+            "--verify" => verify = true,
+            "--set" => set = Some(parse_number(&arg, args.next())?),
+            "--challenge" => number = Some(parse_number(&arg, args.next())?),
+            "--payloads" => match args.next() {
+                Some(dir) => payloads::set_override_dir(dir),
+                None => return Err("--payloads expects a directory".to_string()),
+            },
+            "--wordlist" => match args.next() {
+                Some(file) => payloads::set_file_override(challenge38::WORDLIST, file),
+                None => return Err("--wordlist expects a file".to_string()),
+            },
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    let selected: Vec<&Challenge> = challenges::CHALLENGES
+        .iter()
+        .filter(|c| set.is_none_or(|s| c.set == s))
+        .filter(|c| number.is_none_or(|n| c.number == n))
+        .collect();
+
+    if selected.is_empty() {
+        Err("No challenges match the selection".to_string())
+    } else if verify {
+        Ok(Command::Verify(selected))
+    } else {
+        Ok(Command::Run(selected))
+    }
+}
+
+/// Parse the value following a numeric flag
+///
+fn parse_number(flag: &str, value: Option<String>) -> Result<u32, String> {
+    value
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| format!("{} expects a number", flag))
+}
+
+/// Print every registered challenge
+///
+fn list() {
+    for c in challenges::CHALLENGES.iter() {
+        println!("Set {} Challenge {:>2}: {}", c.set, c.number, c.title);
+    }
+}
+
+/// Solve the selected challenges printing the answers and how long each took.
+/// Returns whether every challenge was solved.
+///
+fn run(selected: &[&Challenge]) -> bool {
+    let mut failed = 0;
+    let mut current_set = 0;
+    for c in selected.iter() {
+        if c.set != current_set {
+            current_set = c.set;
+            println!("=== Set {}", current_set);
+        }
+
+        let start = Instant::now();
+        let result = (c.solve)();
+        let elapsed = start.elapsed();
+
+        println!("  {:>2}. {} ({:.2?})", c.number, c.title, elapsed);
+        match result {
+            Ok(answer) => println!("\t{}", answer.replace('\n', "")),
+            Err(e) => {
+                failed += 1;
+                println!("\tFAILED: {}", e);
+            }
+        }
+    }
 
     println!("=== Finished");
+    failed == 0
+}
+
+/// Solve the selected challenges and check each against its known answer.
+/// Returns whether every challenge passed.
+///
+fn verify(selected: &[&Challenge]) -> bool {
+    let mut failed = 0;
+    for c in selected.iter() {
+        let start = Instant::now();
+        let result = c.verify();
+        let elapsed = start.elapsed();
+
+        match result {
+            Ok(_) => println!("ok      {:>2}. {} ({:.2?})", c.number, c.title, elapsed),
+            Err(failure) => {
+                failed += 1;
+                println!("FAILED  {:>2}. {} ({:.2?})", c.number, c.title, elapsed);
+                println!("\t{}", failure.to_string().replace('\n', ""));
+            }
+        }
+    }
+
+    println!("=== {} passed, {} failed", selected.len() - failed, failed);
+    failed == 0
 }
@@ -0,0 +1,225 @@
+use std::convert::TryInto;
+
+/// Size of the blocks the compression function works on, in bytes
+///
+pub const BLOCK_LEN: usize = 64;
+
+/// Size of the digest in bytes
+///
+pub const DIGEST_LEN: usize = 16;
+
+const INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+/// MD4 (RFC 1320). Like SHA-1 the state can be set directly to resume hashing from a digest.
+///
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: Vec<u8>,
+    //Total bytes processed, including any the state was resumed from
+    length: u64,
+}
+
+impl Md4 {
+    pub fn new() -> Md4 {
+        Md4::from_state(INITIAL_STATE, 0)
+    }
+
+    /// Resume hashing from an internal state after length bytes have been processed. The
+    /// length must be a whole number of blocks (including the padding of the original message).
+    ///
+    pub fn from_state(state: [u32; 4], length: u64) -> Md4 {
+        Md4 {
+            state,
+            buffer: Vec::with_capacity(BLOCK_LEN),
+            length,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend(data);
+        let whole = self.buffer.len() - self.buffer.len() % BLOCK_LEN;
+        for block in self.buffer[..whole].chunks(BLOCK_LEN) {
+            compress(&mut self.state, block);
+        }
+        self.buffer.drain(..whole);
+    }
+
+    pub fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let padding = md4_padding(self.length);
+        self.update(&padding);
+
+        let mut digest = [0u8; DIGEST_LEN];
+        for (chunk, word) in digest.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// MD4 digest of the data
+///
+pub fn md4(data: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut hasher = Md4::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The internal state that produced a digest
+///
+pub fn state_from_digest(digest: &[u8; DIGEST_LEN]) -> [u32; 4] {
+    let mut state = [0u32; 4];
+    for (word, chunk) in state.iter_mut().zip(digest.chunks(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    state
+}
+
+/// The padding MD4 appends to a message of the given length. The same as SHA-1's except the
+/// length in bits is little-endian.
+///
+pub fn md4_padding(message_len: u64) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    while (message_len as usize + padding.len()) % BLOCK_LEN != BLOCK_LEN - 8 {
+        padding.push(0);
+    }
+    padding.extend(&(message_len.wrapping_mul(8)).to_le_bytes());
+    padding
+}
+
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut x = [0u32; 16];
+    for (word, chunk) in x.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let f = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
+    let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+    let h = |x: u32, y: u32, z: u32| x ^ y ^ z;
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    //Round 1
+    for &i in [0, 4, 8, 12].iter() {
+        a = a.wrapping_add(f(b, c, d)).wrapping_add(x[i]).rotate_left(3);
+        d = d
+            .wrapping_add(f(a, b, c))
+            .wrapping_add(x[i + 1])
+            .rotate_left(7);
+        c = c
+            .wrapping_add(f(d, a, b))
+            .wrapping_add(x[i + 2])
+            .rotate_left(11);
+        b = b
+            .wrapping_add(f(c, d, a))
+            .wrapping_add(x[i + 3])
+            .rotate_left(19);
+    }
+
+    //Round 2
+    for &i in [0, 1, 2, 3].iter() {
+        let k = 0x5a82_7999u32;
+        a = a
+            .wrapping_add(g(b, c, d))
+            .wrapping_add(x[i])
+            .wrapping_add(k)
+            .rotate_left(3);
+        d = d
+            .wrapping_add(g(a, b, c))
+            .wrapping_add(x[i + 4])
+            .wrapping_add(k)
+            .rotate_left(5);
+        c = c
+            .wrapping_add(g(d, a, b))
+            .wrapping_add(x[i + 8])
+            .wrapping_add(k)
+            .rotate_left(9);
+        b = b
+            .wrapping_add(g(c, d, a))
+            .wrapping_add(x[i + 12])
+            .wrapping_add(k)
+            .rotate_left(13);
+    }
+
+    //Round 3
+    for &i in [0, 2, 1, 3].iter() {
+        let k = 0x6ed9_eba1u32;
+        a = a
+            .wrapping_add(h(b, c, d))
+            .wrapping_add(x[i])
+            .wrapping_add(k)
+            .rotate_left(3);
+        d = d
+            .wrapping_add(h(a, b, c))
+            .wrapping_add(x[i + 8])
+            .wrapping_add(k)
+            .rotate_left(9);
+        c = c
+            .wrapping_add(h(d, a, b))
+            .wrapping_add(x[i + 4])
+            .wrapping_add(k)
+            .rotate_left(11);
+        b = b
+            .wrapping_add(h(c, d, a))
+            .wrapping_add(x[i + 12])
+            .wrapping_add(k)
+            .rotate_left(15);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d].iter()) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::hex_converter::hex_encode;
+
+#[test]
+fn test_md4_rfc_1320() {
+    let vectors: [(&str, &str); 7] = [
+        ("", "31D6CFE0D16AE931B73C59D7E0C089C0"),
+        ("a", "BDE52CB31DE33E46245E05FBDBD6FB24"),
+        ("abc", "A448017AAF21D8525FC10AE87AA6729D"),
+        ("message digest", "D9130A8164549FE818874806E1C7014B"),
+        (
+            "abcdefghijklmnopqrstuvwxyz",
+            "D79E1C308AA5BBCDEEA8ED63DF412DA9",
+        ),
+        (
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            "043F8582F241DB351CE627E153E7F0E4",
+        ),
+        (
+            "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            "E33B4DDC9C38F2199C3E7B164FCC0536",
+        ),
+    ];
+    for (message, expected) in vectors.iter() {
+        assert_eq!(hex_encode(&md4(message.as_bytes())), *expected);
+    }
+}
+
+#[test]
+fn test_md4_resume_from_state() {
+    let message = b"The quick brown fox";
+    let padding = md4_padding(message.len() as u64);
+    let mut glued = message.to_vec();
+    glued.extend(&padding);
+    glued.extend(b" jumps over the lazy dog");
+
+    let mut resumed = Md4::from_state(
+        state_from_digest(&md4(message)),
+        (message.len() + padding.len()) as u64,
+    );
+    resumed.update(b" jumps over the lazy dog");
+    assert_eq!(resumed.finalize(), md4(&glued));
+}
@@ -0,0 +1,119 @@
+use crate::md4::{self, Md4};
+use crate::sha1::{self, Sha1};
+use crate::sha256::{self, Sha256};
+use std::convert::TryInto;
+
+/// A hash built by feeding padded blocks through a compression function, whose digest is its
+/// final internal state. MACs and the length extension attack are written against this so
+/// they work with any of the hashes.
+///
+pub trait MerkleDamgardHash: Sized {
+    /// Size of a block in bytes
+    ///
+    const BLOCK_LEN: usize;
+
+    /// Size of the digest in bytes
+    ///
+    const DIGEST_LEN: usize;
+
+    fn new() -> Self;
+
+    /// Resume hashing from the state a digest was produced from, after length bytes
+    /// (a whole number of blocks) have been processed. The digest must be DIGEST_LEN bytes.
+    ///
+    fn resume(digest: &[u8], length: u64) -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Vec<u8>;
+
+    /// The padding appended to a message of the given length before the final block
+    ///
+    fn padding(message_len: u64) -> Vec<u8>;
+
+    /// Digest of the data in one go
+    ///
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl MerkleDamgardHash for Md4 {
+    const BLOCK_LEN: usize = md4::BLOCK_LEN;
+    const DIGEST_LEN: usize = md4::DIGEST_LEN;
+
+    fn new() -> Self {
+        Md4::new()
+    }
+
+    fn resume(digest: &[u8], length: u64) -> Self {
+        let digest = digest.try_into().expect("MD4 digest must be 16 bytes");
+        Md4::from_state(md4::state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Md4::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Md4::finalize(self).to_vec()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        md4::md4_padding(message_len)
+    }
+}
+
+impl MerkleDamgardHash for Sha1 {
+    const BLOCK_LEN: usize = sha1::BLOCK_LEN;
+    const DIGEST_LEN: usize = sha1::DIGEST_LEN;
+
+    fn new() -> Self {
+        Sha1::new()
+    }
+
+    fn resume(digest: &[u8], length: u64) -> Self {
+        let digest = digest.try_into().expect("SHA-1 digest must be 20 bytes");
+        Sha1::from_state(sha1::state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Sha1::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha1::finalize(self).to_vec()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        sha1::sha1_padding(message_len)
+    }
+}
+
+impl MerkleDamgardHash for Sha256 {
+    const BLOCK_LEN: usize = sha256::BLOCK_LEN;
+    const DIGEST_LEN: usize = sha256::DIGEST_LEN;
+
+    fn new() -> Self {
+        Sha256::new()
+    }
+
+    fn resume(digest: &[u8], length: u64) -> Self {
+        let digest = digest.try_into().expect("SHA-256 digest must be 32 bytes");
+        Sha256::from_state(sha256::state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Sha256::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha256::finalize(self).to_vec()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        sha256::sha256_padding(message_len)
+    }
+}
@@ -0,0 +1,557 @@
+use crate::block_cipher::BlockCipher;
+use crate::padding::{Padding, PaddingError, Pkcs7};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ModeError {
+    InvalidIvLength,
+    UnalignedData,
+    Padding(PaddingError),
+}
+
+impl Error for ModeError {
+    fn description(&self) -> &str {
+        match *self {
+            ModeError::InvalidIvLength => "IV must be the same size as the cipher block",
+            ModeError::UnalignedData => "Data must be a multiple of the cipher block size",
+            ModeError::Padding(_) => "Decrypted data has invalid padding",
+        }
+    }
+}
+
+impl fmt::Display for ModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ModeError::InvalidIvLength => write!(f, "IV must be the same size as the cipher block"),
+            ModeError::UnalignedData => {
+                write!(f, "Data must be a multiple of the cipher block size")
+            }
+            ModeError::Padding(e) => write!(f, "Decrypted data has invalid padding: {}", e),
+        }
+    }
+}
+
+/// How the CTR counter block is advanced for each block of keystream
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Counter {
+    /// The whole block is a big-endian integer incremented per block (NIST SP 800-38A)
+    BigEndian,
+    /// The first half is a fixed nonce and the second half a little-endian block count (cryptopals)
+    LittleEndianHalf,
+}
+
+impl From<PaddingError> for ModeError {
+    fn from(e: PaddingError) -> Self {
+        ModeError::Padding(e)
+    }
+}
+
+/// Electronic codebook. Each block is encrypted independently after PKCS#7 padding the data
+/// to the block size.
+///
+pub fn ecb_encrypt<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    ecb_encrypt_with(cipher, &Pkcs7, data)
+}
+
+/// Electronic codebook encryption using the given padding scheme
+///
+pub fn ecb_encrypt_with<C: BlockCipher, P: Padding>(
+    cipher: &C,
+    padding: &P,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    let mut encrypted = padding.pad(data, cipher.block_size());
+    check_aligned(cipher, &encrypted)?;
+    for block in encrypted.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(block);
+    }
+    Ok(encrypted)
+}
+
+/// Electronic codebook decryption. The PKCS#7 padding is validated and removed.
+///
+pub fn ecb_decrypt<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    ecb_decrypt_with(cipher, &Pkcs7, data)
+}
+
+/// Electronic codebook decryption using the given padding scheme
+///
+pub fn ecb_decrypt_with<C: BlockCipher, P: Padding>(
+    cipher: &C,
+    padding: &P,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_aligned(cipher, data)?;
+
+    let mut decrypted = data.to_vec();
+    for block in decrypted.chunks_mut(cipher.block_size()) {
+        cipher.decrypt_block(block);
+    }
+
+    let unpadded_len = padding.unpad(&decrypted, cipher.block_size())?.len();
+    decrypted.truncate(unpadded_len);
+    Ok(decrypted)
+}
+
+/// Cipher block chaining. Each plaintext block is xor'd with the previous ciphertext block
+/// (or the IV) before being encrypted. The data is PKCS#7 padded.
+///
+pub fn cbc_encrypt<C: BlockCipher>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    cbc_encrypt_with(cipher, &Pkcs7, iv, data)
+}
+
+/// Cipher block chaining encryption using the given padding scheme
+///
+pub fn cbc_encrypt_with<C: BlockCipher, P: Padding>(
+    cipher: &C,
+    padding: &P,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, iv)?;
+
+    let block_size = cipher.block_size();
+    let mut encrypted = padding.pad(data, block_size);
+    check_aligned(cipher, &encrypted)?;
+    let mut prev = iv.to_vec();
+    for block in encrypted.chunks_mut(block_size) {
+        xor_in_place(block, &prev);
+        cipher.encrypt_block(block);
+        prev.copy_from_slice(block);
+    }
+    Ok(encrypted)
+}
+
+/// Cipher block chaining decryption. The PKCS#7 padding is validated and removed.
+///
+pub fn cbc_decrypt<C: BlockCipher>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    cbc_decrypt_with(cipher, &Pkcs7, iv, data)
+}
+
+/// Cipher block chaining decryption using the given padding scheme
+///
+pub fn cbc_decrypt_with<C: BlockCipher, P: Padding>(
+    cipher: &C,
+    padding: &P,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, iv)?;
+    check_aligned(cipher, data)?;
+
+    let block_size = cipher.block_size();
+    let mut decrypted = data.to_vec();
+    let mut prev = iv.to_vec();
+    for (block, encrypted_block) in decrypted
+        .chunks_mut(block_size)
+        .zip(data.chunks(block_size))
+    {
+        cipher.decrypt_block(block);
+        xor_in_place(block, &prev);
+        prev.copy_from_slice(encrypted_block);
+    }
+
+    let unpadded_len = padding.unpad(&decrypted, block_size)?.len();
+    decrypted.truncate(unpadded_len);
+    Ok(decrypted)
+}
+
+/// Counter mode. The keystream is the encryption of successive counter blocks so encryption
+/// and decryption are the same operation and no padding is needed.
+///
+pub fn ctr<C: BlockCipher>(
+    cipher: &C,
+    initial_counter: &[u8],
+    counter: Counter,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    ctr_at(cipher, initial_counter, counter, 0, data)
+}
+
+/// Counter mode applied to data that starts at the given byte offset into the message. Only
+/// the keystream covering the data is generated so any range can be decrypted or re-encrypted
+/// on its own.
+///
+pub fn ctr_at<C: BlockCipher>(
+    cipher: &C,
+    initial_counter: &[u8],
+    counter: Counter,
+    offset: usize,
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    let keystream = ctr_keystream(cipher, initial_counter, counter, offset, data.len())?;
+    let mut output = data.to_vec();
+    xor_in_place(&mut output, &keystream);
+    Ok(output)
+}
+
+/// The len bytes of CTR keystream starting at the given byte offset. The counter is advanced
+/// straight to the block containing the offset rather than stepping through every block.
+///
+pub fn ctr_keystream<C: BlockCipher>(
+    cipher: &C,
+    initial_counter: &[u8],
+    counter: Counter,
+    offset: usize,
+    len: usize,
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, initial_counter)?;
+
+    let bs = cipher.block_size();
+    let skip = offset % bs;
+    let mut counter_block = initial_counter.to_vec();
+    advance_counter(&mut counter_block, counter, (offset / bs) as u64);
+
+    let mut keystream = Vec::with_capacity(skip + len + bs);
+    while keystream.len() < skip + len {
+        let mut block = counter_block.clone();
+        cipher.encrypt_block(&mut block);
+        keystream.extend(block);
+        increment_counter(&mut counter_block, counter);
+    }
+    Ok(keystream[skip..skip + len].to_vec())
+}
+
+/// Output feedback. The keystream is the IV repeatedly encrypted so, like CTR, encryption and
+/// decryption are the same operation.
+///
+pub fn ofb<C: BlockCipher>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, iv)?;
+
+    let mut output = data.to_vec();
+    let mut keystream = iv.to_vec();
+    for block in output.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(&mut keystream);
+        xor_in_place(block, &keystream);
+    }
+    Ok(output)
+}
+
+/// Full block cipher feedback. The keystream is the encryption of the previous ciphertext
+/// block (or the IV).
+///
+pub fn cfb_encrypt<C: BlockCipher>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, iv)?;
+
+    let mut encrypted = data.to_vec();
+    let mut keystream = iv.to_vec();
+    for block in encrypted.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(&mut keystream);
+        xor_in_place(block, &keystream);
+        keystream[..block.len()].copy_from_slice(block);
+    }
+    Ok(encrypted)
+}
+
+/// Full block cipher feedback decryption
+///
+pub fn cfb_decrypt<C: BlockCipher>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> std::result::Result<Vec<u8>, ModeError> {
+    check_iv(cipher, iv)?;
+
+    let mut decrypted = data.to_vec();
+    let mut keystream = iv.to_vec();
+    for (block, encrypted_block) in decrypted
+        .chunks_mut(cipher.block_size())
+        .zip(data.chunks(cipher.block_size()))
+    {
+        cipher.encrypt_block(&mut keystream);
+        xor_in_place(block, &keystream);
+        keystream[..block.len()].copy_from_slice(encrypted_block);
+    }
+    Ok(decrypted)
+}
+
+/// Advance the counter block by one
+///
+fn increment_counter(counter_block: &mut [u8], counter: Counter) {
+    advance_counter(counter_block, counter, 1);
+}
+
+//Add blocks to the counter with carry, wrapping around at the end of the counter bytes
+fn advance_counter(counter_block: &mut [u8], counter: Counter, blocks: u64) {
+    let mut carry = blocks;
+    let mut add = |b: &mut u8| {
+        let sum = u64::from(*b) + (carry & 0xff);
+        *b = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+        carry != 0
+    };
+    match counter {
+        Counter::BigEndian => {
+            for b in counter_block.iter_mut().rev() {
+                if !add(b) {
+                    break;
+                }
+            }
+        }
+        Counter::LittleEndianHalf => {
+            let half = counter_block.len() / 2;
+            for b in counter_block[half..].iter_mut() {
+                if !add(b) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn xor_in_place(data: &mut [u8], key: &[u8]) {
+    for (d, k) in data.iter_mut().zip(key.iter()) {
+        *d ^= k;
+    }
+}
+
+fn check_iv<C: BlockCipher>(cipher: &C, iv: &[u8]) -> std::result::Result<(), ModeError> {
+    if iv.len() == cipher.block_size() {
+        Ok(())
+    } else {
+        Err(ModeError::InvalidIvLength)
+    }
+}
+
+fn check_aligned<C: BlockCipher>(cipher: &C, data: &[u8]) -> std::result::Result<(), ModeError> {
+    if data.len().is_multiple_of(cipher.block_size()) {
+        Ok(())
+    } else {
+        Err(ModeError::UnalignedData)
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::aes::Aes;
+#[cfg(test)]
+use crate::hex_converter::hex_decode;
+#[cfg(test)]
+use crate::padding::{AnsiX923, Iso10126, Iso7816, NoPadding, ZeroPadding};
+
+//NIST SP 800-38A F.1 - F.5 AES-128 test vectors
+#[cfg(test)]
+const SP800_38A_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+#[cfg(test)]
+const SP800_38A_IV: &str = "000102030405060708090a0b0c0d0e0f";
+#[cfg(test)]
+const SP800_38A_PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+#[cfg(test)]
+fn sp800_38a() -> (Aes, Vec<u8>, Vec<u8>) {
+    let cipher = Aes::new(&hex_decode(SP800_38A_KEY).unwrap()).unwrap();
+    let iv = hex_decode(SP800_38A_IV).unwrap();
+    let plaintext = hex_decode(SP800_38A_PLAINTEXT).unwrap();
+    (cipher, iv, plaintext)
+}
+
+#[test]
+fn test_modes_ecb() {
+    let (cipher, _, plaintext) = sp800_38a();
+    let expected = hex_decode("3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4").unwrap();
+
+    //Aligned data gets a full block of padding after the test vector blocks
+    let encrypted = ecb_encrypt(&cipher, &plaintext).unwrap();
+    assert_eq!(encrypted.len(), plaintext.len() + 16);
+    assert_eq!(encrypted[..plaintext.len()], expected[..]);
+    assert_eq!(ecb_decrypt(&cipher, &encrypted).unwrap(), plaintext);
+}
+
+#[test]
+fn test_modes_cbc() {
+    let (cipher, iv, plaintext) = sp800_38a();
+    let expected = hex_decode("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7").unwrap();
+
+    let encrypted = cbc_encrypt(&cipher, &iv, &plaintext).unwrap();
+    assert_eq!(encrypted.len(), plaintext.len() + 16);
+    assert_eq!(encrypted[..plaintext.len()], expected[..]);
+    assert_eq!(cbc_decrypt(&cipher, &iv, &encrypted).unwrap(), plaintext);
+}
+
+#[test]
+fn test_modes_no_padding() {
+    let (cipher, iv, plaintext) = sp800_38a();
+    let expected = hex_decode("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7").unwrap();
+
+    let encrypted = cbc_encrypt_with(&cipher, &NoPadding, &iv, &plaintext).unwrap();
+    assert_eq!(encrypted, expected);
+    assert_eq!(
+        cbc_decrypt_with(&cipher, &NoPadding, &iv, &encrypted).unwrap(),
+        plaintext
+    );
+    assert_eq!(
+        cbc_encrypt_with(&cipher, &NoPadding, &iv, b"unaligned").unwrap_err(),
+        ModeError::UnalignedData
+    );
+    assert_eq!(
+        ecb_encrypt_with(&cipher, &NoPadding, b"unaligned").unwrap_err(),
+        ModeError::UnalignedData
+    );
+}
+
+#[test]
+fn test_modes_padding_schemes() {
+    let (cipher, iv, _) = sp800_38a();
+    let data = b"Build me up buttercup";
+
+    fn round_trip<P: Padding>(cipher: &Aes, iv: &[u8], padding: &P, data: &[u8]) {
+        let encrypted = cbc_encrypt_with(cipher, padding, iv, data).unwrap();
+        assert_eq!(encrypted.len(), 32);
+        assert_eq!(
+            cbc_decrypt_with(cipher, padding, iv, &encrypted).unwrap(),
+            data
+        );
+
+        let encrypted = ecb_encrypt_with(cipher, padding, data).unwrap();
+        assert_eq!(ecb_decrypt_with(cipher, padding, &encrypted).unwrap(), data);
+    }
+
+    round_trip(&cipher, &iv, &AnsiX923, data);
+    round_trip(&cipher, &iv, &Iso7816, data);
+    round_trip(&cipher, &iv, &Iso10126, data);
+    round_trip(&cipher, &iv, &ZeroPadding, data);
+}
+
+#[test]
+fn test_modes_cfb() {
+    let (cipher, iv, plaintext) = sp800_38a();
+    let expected = hex_decode("3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6").unwrap();
+
+    let encrypted = cfb_encrypt(&cipher, &iv, &plaintext).unwrap();
+    assert_eq!(encrypted, expected);
+    assert_eq!(cfb_decrypt(&cipher, &iv, &encrypted).unwrap(), plaintext);
+}
+
+#[test]
+fn test_modes_ofb() {
+    let (cipher, iv, plaintext) = sp800_38a();
+    let expected = hex_decode("3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e").unwrap();
+
+    let encrypted = ofb(&cipher, &iv, &plaintext).unwrap();
+    assert_eq!(encrypted, expected);
+    assert_eq!(ofb(&cipher, &iv, &encrypted).unwrap(), plaintext);
+}
+
+#[test]
+fn test_modes_ctr() {
+    let (cipher, _, plaintext) = sp800_38a();
+    let counter = hex_decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+    let expected = hex_decode("874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee").unwrap();
+
+    let encrypted = ctr(&cipher, &counter, Counter::BigEndian, &plaintext).unwrap();
+    assert_eq!(encrypted, expected);
+    assert_eq!(
+        ctr(&cipher, &counter, Counter::BigEndian, &encrypted).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn test_modes_ctr_little_endian_partial_block() {
+    //Challenge 18 ciphertext
+    let cipher = Aes::new(b"YELLOW SUBMARINE").unwrap();
+    let encrypted = crate::base64_converter::base64_decode(
+        "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==",
+    )
+    .unwrap();
+
+    let decrypted = ctr(&cipher, &[0; 16], Counter::LittleEndianHalf, &encrypted).unwrap();
+    assert_eq!(
+        decrypted,
+        &b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "[..]
+    );
+}
+
+#[test]
+fn test_modes_ctr_at_offset() {
+    let (cipher, _, plaintext) = sp800_38a();
+    let counter = hex_decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+    let encrypted = ctr(&cipher, &counter, Counter::BigEndian, &plaintext).unwrap();
+
+    //Ranges inside a block, across blocks and across the counter's carry
+    for &(start, end) in [(0, 5), (7, 16), (13, 40), (16, 64), (63, 64)].iter() {
+        let decrypted = ctr_at(
+            &cipher,
+            &counter,
+            Counter::BigEndian,
+            start,
+            &encrypted[start..end],
+        )
+        .unwrap();
+        assert_eq!(decrypted, &plaintext[start..end]);
+    }
+}
+
+#[test]
+fn test_modes_advance_counter() {
+    let mut block = [0xffu8; 16];
+    block[0] = 0;
+    advance_counter(&mut block, Counter::BigEndian, 0x102);
+    let mut expected = [0u8; 16];
+    expected[0] = 1;
+    expected[14] = 1;
+    expected[15] = 1;
+    assert_eq!(block, expected);
+
+    let mut block = [0u8; 16];
+    block[8] = 0xff;
+    advance_counter(&mut block, Counter::LittleEndianHalf, 0x1_0001);
+    let mut expected = [0u8; 16];
+    expected[9] = 1;
+    expected[10] = 1;
+    assert_eq!(block, expected);
+}
+
+#[test]
+fn test_modes_invalid_iv() {
+    let (cipher, _, plaintext) = sp800_38a();
+    let result = cbc_encrypt(&cipher, &[0; 8], &plaintext);
+    assert_eq!(result.unwrap_err(), ModeError::InvalidIvLength);
+}
+
+#[test]
+fn test_modes_cbc_invalid_padding() {
+    let (cipher, iv, plaintext) = sp800_38a();
+    let mut encrypted = cbc_encrypt(&cipher, &iv, &plaintext).unwrap();
+
+    //Flipping a byte of the second to last block flips the same byte of the padding block
+    let idx = encrypted.len() - 18;
+    encrypted[idx] ^= 0x01;
+    let result = cbc_decrypt(&cipher, &iv, &encrypted);
+    assert_eq!(
+        result.unwrap_err(),
+        ModeError::Padding(PaddingError::InconsistentPadding)
+    );
+}
+
+#[test]
+fn test_modes_unaligned() {
+    let (cipher, _, _) = sp800_38a();
+    let result = ecb_decrypt(&cipher, &[0; 17]);
+    assert_eq!(result.unwrap_err(), ModeError::UnalignedData);
+}
@@ -0,0 +1,232 @@
+use crate::error::{CryptoError, Result};
+
+//MT19937 parameters
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER_MASK: u32 = 0x8000_0000;
+const LOWER_MASK: u32 = 0x7fff_ffff;
+const INIT_MULTIPLIER: u32 = 1_812_433_253;
+
+//MT19937-64 parameters
+const N_64: usize = 312;
+const M_64: usize = 156;
+const MATRIX_A_64: u64 = 0xb502_6f5a_a966_19e9;
+const UPPER_MASK_64: u64 = 0xffff_ffff_8000_0000;
+const LOWER_MASK_64: u64 = 0x7fff_ffff;
+const INIT_MULTIPLIER_64: u64 = 6_364_136_223_846_793_005;
+
+/// Number of outputs needed to rebuild the whole state of a 32-bit generator
+///
+pub const STATE_SIZE: usize = N;
+
+/// The 32-bit Mersenne Twister, matching the reference mt19937ar.c init_genrand/genrand_int32
+///
+#[derive(Clone)]
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn new(seed: u32) -> Mt19937 {
+        let mut state = [0u32; N];
+        state[0] = seed;
+        for i in 1..N {
+            let prev = state[i - 1];
+            state[i] = INIT_MULTIPLIER
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(i as u32);
+        }
+        Mt19937 { state, index: N }
+    }
+
+    /// Build a generator that continues from the given internal state, the next output will
+    /// be the first of the next twist
+    ///
+    pub fn from_state(state: [u32; N]) -> Mt19937 {
+        Mt19937 { state, index: N }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+        let y = self.state[self.index];
+        self.index += 1;
+        temper(y)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mut next = self.state[(i + M) % N] ^ (y >> 1);
+            if y & 1 != 0 {
+                next ^= MATRIX_A;
+            }
+            self.state[i] = next;
+        }
+        self.index = 0;
+    }
+}
+
+/// The 64-bit Mersenne Twister, matching the reference mt19937-64.c init_genrand64/genrand64_int64
+///
+#[derive(Clone)]
+pub struct Mt19937_64 {
+    state: [u64; N_64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    pub fn new(seed: u64) -> Mt19937_64 {
+        let mut state = [0u64; N_64];
+        state[0] = seed;
+        for i in 1..N_64 {
+            let prev = state[i - 1];
+            state[i] = INIT_MULTIPLIER_64
+                .wrapping_mul(prev ^ (prev >> 62))
+                .wrapping_add(i as u64);
+        }
+        Mt19937_64 { state, index: N_64 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= (y >> 29) & 0x5555_5555_5555_5555;
+        y ^= (y << 17) & 0x71d6_7fff_eda6_0000;
+        y ^= (y << 37) & 0xfff7_eee0_0000_0000;
+        y ^ (y >> 43)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N_64 {
+            let y = (self.state[i] & UPPER_MASK_64) | (self.state[(i + 1) % N_64] & LOWER_MASK_64);
+            let mut next = self.state[(i + M_64) % N_64] ^ (y >> 1);
+            if y & 1 != 0 {
+                next ^= MATRIX_A_64;
+            }
+            self.state[i] = next;
+        }
+        self.index = 0;
+    }
+}
+
+/// The 32-bit tempering transform applied to each state word on output
+///
+pub fn temper(mut y: u32) -> u32 {
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c_5680;
+    y ^= (y << 15) & 0xefc6_0000;
+    y ^ (y >> 18)
+}
+
+/// Invert the tempering of an output to get back the state word that produced it
+///
+pub fn untemper(mut y: u32) -> u32 {
+    y = undo_shift_right(y, 18, 0xffff_ffff);
+    y = undo_shift_left(y, 15, 0xefc6_0000);
+    y = undo_shift_left(y, 7, 0x9d2c_5680);
+    undo_shift_right(y, 11, 0xffff_ffff)
+}
+
+//Undo y = x ^ ((x >> shift) & mask). The top shift bits of y are already x, each pass
+//recovers the next shift bits.
+fn undo_shift_right(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ ((x >> shift) & mask);
+    }
+    x
+}
+
+//Undo y = x ^ ((x << shift) & mask), working up from the bottom bits
+fn undo_shift_left(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ ((x << shift) & mask);
+    }
+    x
+}
+
+/// Clone a generator from STATE_SIZE consecutive outputs taken from the start of a twist (e.g.
+/// straight after seeding). The clone produces the same outputs as the original from then on.
+///
+pub fn clone_mt19937(outputs: &[u32]) -> Result<Mt19937> {
+    if outputs.len() < N {
+        return Err(CryptoError::NoSolution(
+            "need 624 consecutive outputs to clone the generator",
+        ));
+    }
+    let mut state = [0u32; N];
+    for (s, &output) in state.iter_mut().zip(outputs.iter()) {
+        *s = untemper(output);
+    }
+    Ok(Mt19937::from_state(state))
+}
+
+/// Find the timestamp a generator was seeded with from its first output, by trying every
+/// second in the window up to now
+///
+pub fn crack_timestamp_seed(first_output: u32, now: u32, window: u32) -> Option<u32> {
+    (now.saturating_sub(window)..=now)
+        .rev()
+        .find(|&seed| Mt19937::new(seed).next_u32() == first_output)
+}
+
+/// Unit tests
+///
+#[test]
+fn test_mt19937_reference() {
+    //Default seed of std::mt19937, the 10000th output is specified by the C++ standard
+    let mut mt = Mt19937::new(5489);
+    let first: Vec<u32> = (0..5).map(|_| mt.next_u32()).collect();
+    assert_eq!(
+        first,
+        vec![3499211612, 581869302, 3890346734, 3586334585, 545404204]
+    );
+    let mut mt = Mt19937::new(5489);
+    let last = (0..10000).map(|_| mt.next_u32()).last();
+    assert_eq!(last, Some(4123659995));
+}
+
+#[test]
+fn test_mt19937_64_reference() {
+    let mut mt = Mt19937_64::new(5489);
+    assert_eq!(mt.next_u64(), 14514284786278117030);
+    let mut mt = Mt19937_64::new(5489);
+    let last = (0..10000).map(|_| mt.next_u64()).last();
+    assert_eq!(last, Some(9981545732273789042));
+}
+
+#[test]
+fn test_untemper() {
+    for &y in [0u32, 1, 0xdead_beef, 0xffff_ffff, 0x8000_0001].iter() {
+        assert_eq!(untemper(temper(y)), y);
+    }
+}
+
+#[test]
+fn test_clone_mt19937() {
+    let mut mt = Mt19937::new(crate::random::random_u64() as u32);
+    let outputs: Vec<u32> = (0..STATE_SIZE).map(|_| mt.next_u32()).collect();
+    let mut clone = clone_mt19937(&outputs).unwrap();
+    for _ in 0..2000 {
+        assert_eq!(clone.next_u32(), mt.next_u32());
+    }
+    assert!(clone_mt19937(&outputs[1..]).is_err());
+}
+
+#[test]
+fn test_crack_timestamp_seed() {
+    let output = Mt19937::new(1_500_000_000).next_u32();
+    assert_eq!(
+        crack_timestamp_seed(output, 1_500_000_500, 1000),
+        Some(1_500_000_000)
+    );
+    assert_eq!(crack_timestamp_seed(output, 1_500_002_000, 1000), None);
+}
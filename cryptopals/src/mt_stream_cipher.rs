@@ -0,0 +1,84 @@
+use crate::error::{CryptoError, Result};
+use crate::mt19937::Mt19937;
+
+/// Length of the password reset tokens in bytes
+///
+pub const TOKEN_LEN: usize = 16;
+
+/// Encrypt or decrypt with a stream cipher whose keystream is the low byte of each output of
+/// an MT19937 seeded with a 16-bit key
+///
+pub fn mt_stream_cipher(seed: u16, data: &[u8]) -> Vec<u8> {
+    let mut mt = Mt19937::new(u32::from(seed));
+    data.iter().map(|b| b ^ mt.next_u32() as u8).collect()
+}
+
+/// Recover the 16-bit seed of the MT stream cipher from a ciphertext whose plaintext ends with
+/// known bytes, by trying every seed
+///
+pub fn recover_stream_seed(ciphertext: &[u8], known_suffix: &[u8]) -> Result<u16> {
+    if known_suffix.len() > ciphertext.len() {
+        return Err(CryptoError::NoSolution(
+            "known plaintext is longer than the ciphertext",
+        ));
+    }
+    let offset = ciphertext.len() - known_suffix.len();
+    (0..=u16::MAX)
+        .find(|&seed| mt_stream_cipher(seed, ciphertext)[offset..] == *known_suffix)
+        .ok_or(CryptoError::NoSolution(
+            "no seed produces the known plaintext",
+        ))
+}
+
+/// A "password reset token" made from the keystream of an MT19937 seeded with a timestamp
+///
+pub fn password_reset_token(timestamp: u32) -> Vec<u8> {
+    let mut mt = Mt19937::new(timestamp);
+    (0..TOKEN_LEN).map(|_| mt.next_u32() as u8).collect()
+}
+
+/// The timestamp in the window up to now that the token was generated from, if any
+///
+pub fn token_timestamp(token: &[u8], now: u32, window: u32) -> Option<u32> {
+    (now.saturating_sub(window)..=now).find(|&seed| password_reset_token(seed) == token)
+}
+
+/// Whether a token came from an MT19937 seeded with a timestamp in the window up to now
+///
+pub fn is_timestamp_token(token: &[u8], now: u32, window: u32) -> bool {
+    token_timestamp(token, now, window).is_some()
+}
+
+/// Unit tests
+///
+#[test]
+fn test_mt_stream_cipher_round_trip() {
+    let plaintext = b"Some plaintext to encrypt";
+    let encrypted = mt_stream_cipher(0xbeef, plaintext);
+    assert_ne!(&encrypted[..], &plaintext[..]);
+    assert_eq!(mt_stream_cipher(0xbeef, &encrypted), plaintext.to_vec());
+}
+
+#[test]
+fn test_recover_stream_seed() {
+    let mut plaintext = b"random prefix".to_vec();
+    plaintext.extend(b"AAAAAAAAAAAAAA");
+    let encrypted = mt_stream_cipher(0x1234, &plaintext);
+    assert_eq!(
+        recover_stream_seed(&encrypted, b"AAAAAAAAAAAAAA").unwrap(),
+        0x1234
+    );
+    assert!(recover_stream_seed(b"short", b"AAAAAAAAAAAAAA").is_err());
+}
+
+#[test]
+fn test_is_timestamp_token() {
+    let token = password_reset_token(1_600_000_000);
+    assert!(is_timestamp_token(&token, 1_600_000_100, 300));
+    assert_eq!(
+        token_timestamp(&token, 1_600_000_100, 300),
+        Some(1_600_000_000)
+    );
+    assert!(!is_timestamp_token(&token, 1_600_001_000, 300));
+    assert!(!is_timestamp_token(&[0u8; TOKEN_LEN], 1_600_000_100, 300));
+}
@@ -1,33 +1,399 @@
-/// Returns a copy of the data padded to be aligned to the given size
-/// The length of padding is the data used to actually pad
+use crate::random::random_bytes;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PaddingError {
+    InvalidLength,
+    ZeroPadding,
+    PaddingTooLarge,
+    InconsistentPadding,
+    MissingMarker,
+}
+
+impl Error for PaddingError {
+    fn description(&self) -> &str {
+        match *self {
+            PaddingError::InvalidLength => {
+                "Padded data must be a non-zero multiple of the block size"
+            }
+            PaddingError::ZeroPadding => "Padding length cannot be zero",
+            PaddingError::PaddingTooLarge => "Padding length is larger than the block size",
+            PaddingError::InconsistentPadding => {
+                "Padding bytes do not all match the padding length"
+            }
+            PaddingError::MissingMarker => "Padding does not start with the 0x80 marker byte",
+        }
+    }
+}
+
+impl fmt::Display for PaddingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaddingError::InvalidLength => write!(
+                f,
+                "Padded data must be a non-zero multiple of the block size"
+            ),
+            PaddingError::ZeroPadding => write!(f, "Padding length cannot be zero"),
+            PaddingError::PaddingTooLarge => {
+                write!(f, "Padding length is larger than the block size")
+            }
+            PaddingError::InconsistentPadding => {
+                write!(f, "Padding bytes do not all match the padding length")
+            }
+            PaddingError::MissingMarker => {
+                write!(f, "Padding does not start with the 0x80 marker byte")
+            }
+        }
+    }
+}
+
+/// A scheme for padding data to a multiple of the block size and removing it again
+///
+pub trait Padding {
+    /// Returns a copy of the data padded to a multiple of the block size.
+    /// Schemes that add padding panic if the block size is zero, and those that store the
+    /// padding length in a byte also panic if it is above 255.
+    ///
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8>;
+
+    /// Strips the padding, validating that it is well formed
+    ///
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError>;
+}
+
+/// PKCS#7: each padding byte is the length of the padding
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Pkcs7;
+
+/// ANSI X9.23: zero bytes followed by a final byte holding the length of the padding
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct AnsiX923;
+
+/// ISO/IEC 7816-4: a 0x80 marker byte followed by zero bytes
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Iso7816;
+
+/// ISO 10126: random bytes followed by a final byte holding the length of the padding
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Iso10126;
+
+/// Zero bytes up to the block size, none if the data is already aligned.
+/// Trailing zeros in the data itself are indistinguishable from padding and are also removed.
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ZeroPadding;
+
+/// No padding, the data must already be a multiple of the block size
+///
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoPadding;
+
+impl Padding for Pkcs7 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        pkcs7_pad(data, block_size)
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        pkcs7_unpad(data, block_size)
+    }
+}
+
+impl Padding for AnsiX923 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        let padding = padding_len(data, block_size);
+        let mut padded = Vec::with_capacity(data.len() + padding);
+        padded.extend(data);
+        padded.extend((1..padding).map(|_| 0));
+        padded.push(padding as u8);
+        padded
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        let (unpadded, pad_bytes) = split_length_padding(data, block_size)?;
+        if pad_bytes[..pad_bytes.len() - 1].iter().any(|&b| b != 0) {
+            return Err(PaddingError::InconsistentPadding);
+        }
+        Ok(unpadded)
+    }
+}
+
+impl Padding for Iso7816 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        let padding = padding_len(data, block_size);
+        let mut padded = Vec::with_capacity(data.len() + padding);
+        padded.extend(data);
+        padded.push(0x80);
+        padded.extend((1..padding).map(|_| 0));
+        padded
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        check_length(data, block_size)?;
+
+        //The marker must be within the last block with only zeros after it
+        let last_block = &data[data.len() - block_size..];
+        match last_block.iter().rposition(|&b| b != 0) {
+            Some(idx) if last_block[idx] == 0x80 => Ok(&data[..data.len() - block_size + idx]),
+            _ => Err(PaddingError::MissingMarker),
+        }
+    }
+}
+
+impl Padding for Iso10126 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        let padding = padding_len(data, block_size);
+        let mut padded = Vec::with_capacity(data.len() + padding);
+        padded.extend(data);
+        padded.extend(random_bytes(padding - 1));
+        padded.push(padding as u8);
+        padded
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        //The filler is random so only the length can be validated
+        let (unpadded, _) = split_length_padding(data, block_size)?;
+        Ok(unpadded)
+    }
+}
+
+impl Padding for ZeroPadding {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        assert!(block_size > 0, "Block size must be non-zero");
+        let padding = (block_size - (data.len() % block_size)) % block_size;
+        let mut padded = Vec::with_capacity(data.len() + padding);
+        padded.extend(data);
+        padded.extend((0..padding).map(|_| 0));
+        padded
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        check_length(data, block_size)?;
+        let last_block_start = data.len() - block_size;
+        let end = data[last_block_start..]
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(last_block_start, |idx| last_block_start + idx + 1);
+        Ok(&data[..end])
+    }
+}
+
+impl Padding for NoPadding {
+    fn pad(&self, data: &[u8], _block_size: usize) -> Vec<u8> {
+        data.to_vec()
+    }
+
+    fn unpad<'a>(
+        &self,
+        data: &'a [u8],
+        block_size: usize,
+    ) -> std::result::Result<&'a [u8], PaddingError> {
+        if data.len().is_multiple_of(block_size) {
+            Ok(data)
+        } else {
+            Err(PaddingError::InvalidLength)
+        }
+    }
+}
+
+/// Returns a copy of the data PKCS#7 padded to be aligned to the given block size.
+/// The value of each padding byte is the length of the padding. Aligned data gets a full
+/// block of padding so the padding can always be removed unambiguously.
+/// Panics if the block size is not 1-255.
 ///
-pub fn padded(data: &[u8], alignment: usize) -> Vec<u8> {
-    let padding = (alignment - (data.len() % alignment)) % alignment;
-    let padded_len = data.len() + padding;
-    let mut padded = Vec::with_capacity(padded_len);
+pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    let padding = padding_len(data, block_size);
+    let mut padded = Vec::with_capacity(data.len() + padding);
     padded.extend(data);
     padded.extend((0..padding).map(|_| padding as u8));
     padded
 }
 
+/// Strips the PKCS#7 padding, validating that it is well formed
+///
+pub fn pkcs7_unpad(data: &[u8], block_size: usize) -> std::result::Result<&[u8], PaddingError> {
+    let (unpadded, pad_bytes) = split_length_padding(data, block_size)?;
+    if pad_bytes.iter().any(|&b| b as usize != pad_bytes.len()) {
+        return Err(PaddingError::InconsistentPadding);
+    }
+    Ok(unpadded)
+}
+
+/// Number of bytes of padding needed by the schemes that always add at least one byte.
+/// Aligned data gets a full block.
+///
+fn padding_len(data: &[u8], block_size: usize) -> usize {
+    assert!(
+        block_size > 0 && block_size < 256,
+        "Padding block size must be 1-255"
+    );
+    block_size - (data.len() % block_size)
+}
+
+fn check_length(data: &[u8], block_size: usize) -> std::result::Result<(), PaddingError> {
+    if data.is_empty() || !data.len().is_multiple_of(block_size) {
+        Err(PaddingError::InvalidLength)
+    } else {
+        Ok(())
+    }
+}
+
+/// Split the data from the padding for the schemes where the last byte is the padding length
+///
+fn split_length_padding(
+    data: &[u8],
+    block_size: usize,
+) -> std::result::Result<(&[u8], &[u8]), PaddingError> {
+    check_length(data, block_size)?;
+
+    let padding = data[data.len() - 1] as usize;
+    if padding == 0 {
+        return Err(PaddingError::ZeroPadding);
+    }
+    if padding > block_size {
+        return Err(PaddingError::PaddingTooLarge);
+    }
+
+    Ok(data.split_at(data.len() - padding))
+}
+
 /// Unit tests
 ///
 #[test]
-fn test_padding_no_padding() {
-    let p = padded(b"01234567", 8);
-    assert!(p.len() == 8);
+fn test_padding_aligned_adds_full_block() {
+    let p = pkcs7_pad(b"01234567", 8);
+    assert!(p.len() == 16);
+    assert!(p[8..].iter().all(|&b| b == 8));
 }
 
 #[test]
 fn test_padding_smaller_alignment() {
-    let p = padded(b"01234567", 5);
+    let p = pkcs7_pad(b"01234567", 5);
     assert!(p.len() == 10);
     assert!(p[9] == 2);
 }
 
 #[test]
 fn test_padding_larger_alignment() {
-    let p = padded(b"01234567", 10);
+    let p = pkcs7_pad(b"01234567", 10);
     assert!(p.len() == 10);
     assert!(p[9] == 2);
 }
+
+#[test]
+fn test_unpadding_valid() {
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x04", 16);
+    assert_eq!(unpadded.unwrap(), b"ICE ICE BABY");
+
+    let p = pkcs7_pad(b"01234567", 8);
+    assert_eq!(pkcs7_unpad(&p, 8).unwrap(), b"01234567");
+}
+
+#[test]
+fn test_unpadding_invalid_length() {
+    assert_eq!(pkcs7_unpad(b"", 16), Err(PaddingError::InvalidLength));
+    assert_eq!(
+        pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04", 16),
+        Err(PaddingError::InvalidLength)
+    );
+}
+
+#[test]
+fn test_unpadding_zero() {
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x00", 16);
+    assert_eq!(unpadded, Err(PaddingError::ZeroPadding));
+}
+
+#[test]
+fn test_unpadding_too_large() {
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x11", 16);
+    assert_eq!(unpadded, Err(PaddingError::PaddingTooLarge));
+}
+
+#[test]
+fn test_unpadding_inconsistent() {
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x05\x05\x05\x05", 16);
+    assert_eq!(unpadded, Err(PaddingError::InconsistentPadding));
+
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x01\x02\x03\x04", 16);
+    assert_eq!(unpadded, Err(PaddingError::InconsistentPadding));
+}
+
+#[test]
+fn test_padding_ansi_x923() {
+    let p = AnsiX923.pad(b"ICE ICE BABY", 16);
+    assert_eq!(p, b"ICE ICE BABY\x00\x00\x00\x04");
+    assert_eq!(AnsiX923.unpad(&p, 16).unwrap(), b"ICE ICE BABY");
+    assert_eq!(
+        AnsiX923.unpad(b"ICE ICE BABY\x00\x01\x00\x04", 16),
+        Err(PaddingError::InconsistentPadding)
+    );
+}
+
+#[test]
+fn test_padding_iso7816() {
+    let p = Iso7816.pad(b"ICE ICE BABY", 16);
+    assert_eq!(p, b"ICE ICE BABY\x80\x00\x00\x00");
+    assert_eq!(Iso7816.unpad(&p, 16).unwrap(), b"ICE ICE BABY");
+    assert_eq!(Iso7816.pad(b"0123", 4), b"0123\x80\x00\x00\x00");
+    assert_eq!(
+        Iso7816.unpad(b"ICE ICE BABY\x81\x00\x00\x00", 16),
+        Err(PaddingError::MissingMarker)
+    );
+    assert_eq!(
+        Iso7816.unpad(b"\x00\x00\x00\x00", 4),
+        Err(PaddingError::MissingMarker)
+    );
+}
+
+#[test]
+fn test_padding_iso10126() {
+    let p = Iso10126.pad(b"ICE ICE BABY", 16);
+    assert_eq!(p.len(), 16);
+    assert_eq!(p[15], 4);
+    assert_eq!(Iso10126.unpad(&p, 16).unwrap(), b"ICE ICE BABY");
+    assert_eq!(
+        Iso10126.unpad(b"ICE ICE BABY\x00\x01\x00\x14", 16),
+        Err(PaddingError::PaddingTooLarge)
+    );
+}
+
+#[test]
+fn test_padding_zero() {
+    let p = ZeroPadding.pad(b"ICE ICE BABY", 16);
+    assert_eq!(p, b"ICE ICE BABY\x00\x00\x00\x00");
+    assert_eq!(ZeroPadding.unpad(&p, 16).unwrap(), b"ICE ICE BABY");
+    assert_eq!(ZeroPadding.pad(b"0123", 4), b"0123");
+    assert_eq!(
+        ZeroPadding.unpad(b"ICE", 16),
+        Err(PaddingError::InvalidLength)
+    );
+}
@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Environment variable that can point at an alternative payloads directory
+///
+pub const PAYLOADS_DIR_ENV: &str = "CRYPTOPALS_PAYLOADS";
+
+/// Payloads directory shipped with the crate, independent of the working directory
+///
+const CRATE_PAYLOADS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/payloads");
+
+static OVERRIDE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+static OVERRIDE_FILES: Mutex<Vec<(String, PathBuf)>> = Mutex::new(Vec::new());
+
+#[cfg(feature = "embed-payloads")]
+static EMBEDDED: &[(&str, &str)] = &[
+    (
+        "set1/challenge4.txt",
+        include_str!("../payloads/set1/challenge4.txt"),
+    ),
+    (
+        "set1/challenge6.txt",
+        include_str!("../payloads/set1/challenge6.txt"),
+    ),
+    (
+        "set1/challenge7.txt",
+        include_str!("../payloads/set1/challenge7.txt"),
+    ),
+    (
+        "set1/challenge8.txt",
+        include_str!("../payloads/set1/challenge8.txt"),
+    ),
+    (
+        "set2/challenge10.txt",
+        include_str!("../payloads/set2/challenge10.txt"),
+    ),
+    (
+        "set5/challenge38.txt",
+        include_str!("../payloads/set5/challenge38.txt"),
+    ),
+];
+
+#[derive(Debug)]
+pub enum PayloadError {
+    Missing(PathBuf),
+    Empty(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl Error for PayloadError {
+    fn description(&self) -> &str {
+        match *self {
+            PayloadError::Missing(_) => "Payload file does not exist",
+            PayloadError::Empty(_) => "Payload file is empty",
+            PayloadError::Io(_, _) => "Payload file could not be read",
+        }
+    }
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PayloadError::Missing(path) => {
+                write!(f, "Payload file {} does not exist", path.display())
+            }
+            PayloadError::Empty(path) => write!(f, "Payload file {} is empty", path.display()),
+            PayloadError::Io(path, e) => {
+                write!(
+                    f,
+                    "Payload file {} could not be read: {}",
+                    path.display(),
+                    e
+                )
+            }
+        }
+    }
+}
+
+/// Use the given directory for payloads in preference to the environment and the crate payloads
+///
+pub fn set_override_dir<P: Into<PathBuf>>(dir: P) {
+    *OVERRIDE_DIR.lock().unwrap() = Some(dir.into());
+}
+
+/// Read the named payload from the given file instead of the payloads directory
+///
+pub fn set_file_override<P: Into<PathBuf>>(name: &str, file: P) {
+    let mut files = OVERRIDE_FILES.lock().unwrap();
+    files.retain(|(n, _)| n != name);
+    files.push((name.to_string(), file.into()));
+}
+
+/// The directory payloads are read from. In order of preference: the override directory,
+/// the directory named by CRYPTOPALS_PAYLOADS and then the payloads shipped with the crate.
+///
+pub fn payloads_dir() -> PathBuf {
+    if let Some(dir) = OVERRIDE_DIR.lock().unwrap().as_ref() {
+        return dir.clone();
+    }
+
+    match std::env::var_os(PAYLOADS_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(CRATE_PAYLOADS_DIR),
+    }
+}
+
+/// Load the named payload (e.g. "set1/challenge4.txt"). A file given for the payload with
+/// set_file_override takes precedence over everything else.
+/// When built with the embed-payloads feature the payloads are compiled into the binary and are
+/// only read from disk if an alternative directory has been given.
+///
+pub fn load(name: &str) -> std::result::Result<String, PayloadError> {
+    let file = OVERRIDE_FILES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, file)| file.clone());
+    if let Some(file) = file {
+        return load_file(file);
+    }
+
+    #[cfg(feature = "embed-payloads")]
+    {
+        let overridden =
+            OVERRIDE_DIR.lock().unwrap().is_some() || std::env::var_os(PAYLOADS_DIR_ENV).is_some();
+        if !overridden {
+            if let Some((_, contents)) = EMBEDDED.iter().find(|(n, _)| *n == name) {
+                return check_not_empty(Path::new(name), contents.to_string());
+            }
+        }
+    }
+
+    load_from(&payloads_dir(), name)
+}
+
+/// Load the named payload from the given payloads directory
+///
+fn load_from(dir: &Path, name: &str) -> std::result::Result<String, PayloadError> {
+    load_file(dir.join(name))
+}
+
+fn load_file(path: PathBuf) -> std::result::Result<String, PayloadError> {
+    let contents = fs::read_to_string(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => PayloadError::Missing(path.clone()),
+        _ => PayloadError::Io(path.clone(), e),
+    })?;
+
+    check_not_empty(&path, contents)
+}
+
+/// A payload with no content is never valid
+///
+fn check_not_empty(path: &Path, contents: String) -> std::result::Result<String, PayloadError> {
+    if contents.trim().is_empty() {
+        Err(PayloadError::Empty(path.to_path_buf()))
+    } else {
+        Ok(contents)
+    }
+}
+
+/// Unit tests
+///
+#[test]
+fn test_payloads_load_from_crate_dir() {
+    let payload = load_from(Path::new(CRATE_PAYLOADS_DIR), "set1/challenge4.txt");
+    assert!(payload.is_ok());
+    assert_eq!(payload.unwrap().lines().count(), 327);
+}
+
+#[test]
+fn test_payloads_missing() {
+    let payload = load_from(Path::new(CRATE_PAYLOADS_DIR), "set1/missing.txt");
+    assert!(matches!(payload, Err(PayloadError::Missing(_))));
+}
+
+#[test]
+fn test_payloads_empty() {
+    let dir = std::env::temp_dir().join(format!("cryptopals-payloads-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("empty.txt"), "\n").unwrap();
+
+    let payload = load_from(&dir, "empty.txt");
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(payload, Err(PayloadError::Empty(_))));
+}
+
+#[test]
+fn test_payloads_file_override() {
+    let file = std::env::temp_dir().join(format!("cryptopals-override-{}", std::process::id()));
+    fs::write(&file, "words\n").unwrap();
+
+    set_file_override("test/override.txt", &file);
+    let payload = load("test/override.txt");
+    fs::remove_file(&file).unwrap();
+    assert_eq!(payload.unwrap(), "words\n");
+}
@@ -0,0 +1,120 @@
+use crate::biguint::BigUint;
+
+//Rounds of Miller-Rabin, each of which lets a composite through with probability at most 1/4
+const MILLER_RABIN_ROUNDS: usize = 40;
+
+//Candidates are checked against the primes below this before the expensive test
+const TRIAL_DIVISION_LIMIT: u64 = 1000;
+
+/// Whether n is prime, with a chance of a false positive of at most 4^-rounds.
+/// Random bases are used so the answer doesn't depend on a fixed set of witnesses.
+///
+pub fn is_probable_prime(n: &BigUint, rounds: usize) -> bool {
+    let two = BigUint::from_u64(2);
+    if *n < two {
+        return false;
+    }
+    for p in small_primes() {
+        let p = BigUint::from_u64(p);
+        if *n == p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+
+    //n - 1 = 2^s * d with d odd
+    let n_minus_one = n - &BigUint::one();
+    let s = (0..).find(|&i| n_minus_one.bit(i)).unwrap();
+    let d = &n_minus_one >> s;
+
+    'witness: for _ in 0..rounds {
+        let a = BigUint::random_range(&two, &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A random prime of exactly the given number of bits. The top two bits are set so the
+/// product of two such primes has exactly twice as many bits.
+///
+pub fn generate_prime(bits: usize) -> BigUint {
+    assert!(bits >= 3, "Primes must be at least 3 bits");
+    let top = &(&BigUint::one() << (bits - 1)) + &(&BigUint::one() << (bits - 2));
+    let bound = BigUint::one() << (bits - 2);
+    loop {
+        let mut candidate = &top + &BigUint::random_below(&bound);
+        if candidate.is_even() {
+            candidate = &candidate + &BigUint::one();
+        }
+        if is_probable_prime(&candidate, MILLER_RABIN_ROUNDS) {
+            return candidate;
+        }
+    }
+}
+
+//Primes below TRIAL_DIVISION_LIMIT by the sieve of Eratosthenes
+fn small_primes() -> Vec<u64> {
+    let limit = TRIAL_DIVISION_LIMIT as usize;
+    let mut composite = vec![false; limit];
+    let mut primes = Vec::new();
+    for i in 2..limit {
+        if !composite[i] {
+            primes.push(i as u64);
+            for multiple in (i * i..limit).step_by(i) {
+                composite[multiple] = true;
+            }
+        }
+    }
+    primes
+}
+
+/// Unit tests
+///
+#[test]
+fn test_prime_small() {
+    let primes: Vec<u64> = (0..60)
+        .filter(|&n| is_probable_prime(&BigUint::from_u64(n), 10))
+        .collect();
+    assert_eq!(
+        primes,
+        vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59]
+    );
+}
+
+#[test]
+fn test_prime_large() {
+    //2^127 - 1 is a Mersenne prime, 2^128 + 1 is composite with no small factors
+    let mersenne = &(BigUint::one() << 127) - &BigUint::one();
+    assert!(is_probable_prime(&mersenne, MILLER_RABIN_ROUNDS));
+    let fermat = &(BigUint::one() << 128) + &BigUint::one();
+    assert!(!is_probable_prime(&fermat, MILLER_RABIN_ROUNDS));
+
+    //A Carmichael number (1171 * 2341 * 3511) with no factors small enough for trial
+    //division fools the Fermat test but not Miller-Rabin
+    assert!(!is_probable_prime(
+        &BigUint::from_u64(9_624_742_921),
+        MILLER_RABIN_ROUNDS
+    ));
+}
+
+#[test]
+fn test_prime_generate() {
+    for &bits in &[16, 64, 256] {
+        let p = generate_prime(bits);
+        assert_eq!(p.bits(), bits);
+        assert!(p.bit(bits - 2));
+        assert!(is_probable_prime(&p, MILLER_RABIN_ROUNDS));
+    }
+}
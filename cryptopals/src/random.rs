@@ -0,0 +1,54 @@
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+thread_local! {
+    static STATE: (RandomState, Cell<u64>) = (RandomState::new(), Cell::new(0));
+}
+
+/// Random u64 from the randomly keyed SipHash that backs std's HashMap.
+/// Good enough for generating keys and noise for the challenges, not for real use.
+///
+pub fn random_u64() -> u64 {
+    STATE.with(|(state, counter)| {
+        let mut hasher = state.build_hasher();
+        hasher.write_u64(counter.get());
+        counter.set(counter.get().wrapping_add(1));
+        hasher.finish()
+    })
+}
+
+/// Random number in the range [low, high)
+///
+pub fn random_range(low: usize, high: usize) -> usize {
+    assert!(low < high, "Random range must not be empty");
+    low + (random_u64() % (high - low) as u64) as usize
+}
+
+/// A buffer of random bytes
+///
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        bytes.extend(&random_u64().to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// Unit tests
+///
+#[test]
+fn test_random_bytes_len_and_varies() {
+    let a = random_bytes(21);
+    let b = random_bytes(21);
+    assert_eq!(a.len(), 21);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_random_range_bounds() {
+    assert!((0..1000)
+        .map(|_| random_range(5, 11))
+        .all(|r| (5..11).contains(&r)));
+}
@@ -0,0 +1,106 @@
+use crate::biguint::BigUint;
+use crate::error::{CryptoError, Result};
+use crate::rsa::RsaPublicKey;
+
+/// The x mod the product of the moduli with x = r_i mod m_i for each (r_i, m_i), by the
+/// Chinese remainder theorem. None if the moduli aren't pairwise coprime.
+///
+pub fn crt(residues: &[(BigUint, BigUint)]) -> Option<BigUint> {
+    let product = residues
+        .iter()
+        .fold(BigUint::one(), |product, (_, m)| &product * m);
+
+    let mut result = BigUint::zero();
+    for (r, m) in residues {
+        //m_s is the product of the other moduli, and m_s * (m_s^-1 mod m) is 1 mod m but 0
+        //mod the others
+        let m_s = &product / m;
+        let inverse = (&m_s % m).modinv(m)?;
+        result = &(&result + &(&(r * &m_s) * &inverse)) % &product;
+    }
+    Some(result)
+}
+
+/// Håstad's broadcast attack. The same message encrypted without padding under e keys that
+/// all use exponent e gives m^e mod the product of the moduli by CRT. As m is smaller than
+/// each modulus, m^e is smaller than the product and is recovered exactly, leaving an
+/// integer e-th root.
+///
+pub fn broadcast_attack(ciphertexts: &[(BigUint, RsaPublicKey)]) -> Result<BigUint> {
+    let e = match ciphertexts.first() {
+        Some((_, key)) => key.e.clone(),
+        None => return Err(CryptoError::NoSolution("no ciphertexts")),
+    };
+    if ciphertexts.iter().any(|(_, key)| key.e != e) {
+        return Err(CryptoError::NoSolution("keys use different exponents"));
+    }
+    let root = match e.to_u64() {
+        Some(root) if root < 2 => return Err(CryptoError::NoSolution("exponent is less than 2")),
+        Some(root) if root <= ciphertexts.len() as u64 => root as u32,
+        _ => {
+            return Err(CryptoError::NoSolution(
+                "fewer ciphertexts than the exponent",
+            ))
+        }
+    };
+
+    let residues: Vec<(BigUint, BigUint)> = ciphertexts
+        .iter()
+        .map(|(c, key)| (c.clone(), key.n.clone()))
+        .collect();
+    let power = crt(&residues).ok_or(CryptoError::NoSolution("moduli share a factor"))?;
+
+    let message = power.nth_root(root);
+    if message.pow(root) != power {
+        return Err(CryptoError::NoSolution("not a perfect power"));
+    }
+    Ok(message)
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::rsa::RsaPrivateKey;
+
+#[test]
+fn test_rsa_broadcast_crt() {
+    let residues = [
+        (BigUint::from_u64(2), BigUint::from_u64(3)),
+        (BigUint::from_u64(3), BigUint::from_u64(5)),
+        (BigUint::from_u64(2), BigUint::from_u64(7)),
+    ];
+    assert_eq!(crt(&residues), Some(BigUint::from_u64(23)));
+
+    let shared = [
+        (BigUint::from_u64(1), BigUint::from_u64(4)),
+        (BigUint::from_u64(1), BigUint::from_u64(6)),
+    ];
+    assert_eq!(crt(&shared), None);
+}
+
+#[test]
+fn test_rsa_broadcast_attack() {
+    let message = BigUint::from_bytes_be(b"Attack at dawn");
+    let ciphertexts: Vec<(BigUint, RsaPublicKey)> = (0..3)
+        .map(|_| {
+            let key = RsaPrivateKey::generate(256, 3).unwrap().public_key();
+            (key.encrypt(&message).unwrap(), key)
+        })
+        .collect();
+    assert_eq!(broadcast_attack(&ciphertexts).unwrap(), message);
+    assert!(broadcast_attack(&ciphertexts[..2]).is_err());
+}
+
+#[test]
+fn test_rsa_broadcast_attack_small_exponent() {
+    for &e in &[0, 1] {
+        let key = RsaPublicKey {
+            n: BigUint::from_u64(3233),
+            e: BigUint::from_u64(e),
+        };
+        assert!(matches!(
+            broadcast_attack(&[(BigUint::from_u64(42), key)]),
+            Err(CryptoError::NoSolution(_))
+        ));
+    }
+}
@@ -1,12 +1,15 @@
+use crate::error::{CryptoError, Result};
 use crate::hex_converter::hex_decode;
 use crate::xor_decrypter::single_char_xor_plaintext_decrypt;
 
 /// Find the xor 'key' by 'scoring' plain text (char frequency, etc) and decrypt
 ///
-pub fn solve() -> String {
+pub fn solve() -> Result<String> {
     let encrypted =
-        hex_decode("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736").unwrap();
+        hex_decode("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736")?;
 
-    let (decrypted, _, _) = single_char_xor_plaintext_decrypt(&encrypted).unwrap();
-    String::from_utf8(decrypted).unwrap()
+    let (decrypted, _, _) = single_char_xor_plaintext_decrypt(&encrypted).ok_or(
+        CryptoError::NoSolution("no single character key gives plaintext"),
+    )?;
+    Ok(String::from_utf8(decrypted)?)
 }
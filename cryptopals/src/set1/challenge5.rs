@@ -1,12 +1,13 @@
+use crate::error::Result;
 use crate::hex_converter::hex_encode;
 use crate::xor_encrypter::xor_encrypt;
 
 /// Repeating XOR key
 ///
-pub fn solve() -> String {
+pub fn solve() -> Result<String> {
     let encrypted = xor_encrypt(
         "Burning 'em, if you ain't quick and nimble\nI go crazy when I hear a cymbal",
         "ICE",
     );
-    hex_encode(&encrypted)
+    Ok(hex_encode(&encrypted))
 }
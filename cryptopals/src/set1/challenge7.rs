@@ -1,15 +1,17 @@
+use crate::aes::Aes;
 use crate::base64_converter::base64_decode;
-use openssl::symm::Cipher;
-use std::fs;
+use crate::error::Result;
+use crate::modes::ecb_decrypt;
+use crate::payloads;
 
 /// Decrypt the base64 encoded file using AES-128 ECB key
 ///
-pub fn solve() -> String {
-    let encoded = fs::read_to_string("payloads/set1/challenge7.txt").unwrap();
-    let decoded = base64_decode(&encoded).unwrap();
+pub fn solve() -> Result<String> {
+    let encoded = payloads::load("set1/challenge7.txt")?;
+    let decoded = base64_decode(&encoded)?;
 
-    let key = b"YELLOW SUBMARINE";
-    let cipher = Cipher::aes_128_ecb();
-    let decrypted = openssl::symm::decrypt(cipher, &key[..], None, &decoded).unwrap();
-    String::from_utf8(decrypted).unwrap()
+    let cipher = Aes::new(b"YELLOW SUBMARINE")?;
+    let decrypted = ecb_decrypt(&cipher, &decoded)?;
+
+    Ok(String::from_utf8(decrypted)?)
 }
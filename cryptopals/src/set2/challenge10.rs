@@ -0,0 +1,17 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::base64_converter::base64_decode;
+use crate::error::Result;
+use crate::modes::cbc_decrypt;
+use crate::payloads;
+
+/// Decrypt the base64 encoded file using AES-128 in CBC mode with an all zero IV
+///
+pub fn solve() -> Result<String> {
+    let encoded = payloads::load("set2/challenge10.txt")?;
+    let decoded = base64_decode(&encoded)?;
+
+    let cipher = Aes::new(b"YELLOW SUBMARINE")?;
+    let decrypted = cbc_decrypt(&cipher, &[0; BLOCK_SIZE], &decoded)?;
+
+    Ok(String::from_utf8(decrypted)?)
+}
@@ -0,0 +1,70 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::ecb_detector::detect_ecb;
+use crate::error::{CryptoError, Result};
+use crate::modes::{cbc_encrypt, ecb_encrypt};
+use crate::random::{random_bytes, random_range, random_u64};
+
+const NUM_TRIALS: usize = 1000;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BlockMode {
+    Ecb,
+    Cbc,
+}
+
+/// Detect whether a black box is encrypting with ECB or CBC over many trials
+///
+pub fn solve() -> Result<String> {
+    let correct = (0..NUM_TRIALS)
+        .filter(|_| {
+            let mut used = BlockMode::Ecb;
+            let detected = detect_mode(|input| {
+                let (encrypted, mode) = encryption_oracle(input);
+                used = mode;
+                encrypted
+            });
+            detected == used
+        })
+        .count();
+
+    if correct != NUM_TRIALS {
+        return Err(CryptoError::NoSolution("mode was misclassified"));
+    }
+
+    Ok(format!(
+        "Detected ECB/CBC correctly in {} of {} trials",
+        correct, NUM_TRIALS
+    ))
+}
+
+/// Encrypts the input under a random key, randomly choosing ECB or CBC (with a random IV)
+/// and surrounding the input with 5-10 random bytes either side.
+/// Returns the mode that was used so callers can check the detector.
+///
+pub fn encryption_oracle(input: &[u8]) -> (Vec<u8>, BlockMode) {
+    let cipher = Aes::new(&random_bytes(BLOCK_SIZE)).unwrap();
+
+    let mut plaintext = random_bytes(random_range(5, 11));
+    plaintext.extend(input);
+    plaintext.extend(random_bytes(random_range(5, 11)));
+
+    if random_u64() & 1 == 0 {
+        (ecb_encrypt(&cipher, &plaintext).unwrap(), BlockMode::Ecb)
+    } else {
+        let iv = random_bytes(BLOCK_SIZE);
+        let encrypted = cbc_encrypt(&cipher, &iv, &plaintext).unwrap();
+        (encrypted, BlockMode::Cbc)
+    }
+}
+
+/// Chooses input so that whatever the prefix length, at least two whole blocks of
+/// identical plaintext are encrypted, which only ECB turns into identical ciphertext
+///
+pub fn detect_mode<F: FnMut(&[u8]) -> Vec<u8>>(mut oracle: F) -> BlockMode {
+    let input = [b'A'; 3 * BLOCK_SIZE];
+    if detect_ecb(&oracle(&input), BLOCK_SIZE).is_ecb() {
+        BlockMode::Ecb
+    } else {
+        BlockMode::Cbc
+    }
+}
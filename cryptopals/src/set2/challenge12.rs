@@ -0,0 +1,24 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::base64_converter::base64_decode;
+use crate::ecb_byte_at_a_time::byte_at_a_time_decrypt;
+use crate::error::Result;
+use crate::modes::ecb_encrypt;
+use crate::random::random_bytes;
+
+pub const SECRET: &str = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
+
+/// Decrypt the secret appended to our input by an ECB oracle one byte at a time
+///
+pub fn solve() -> Result<String> {
+    let secret = base64_decode(SECRET)?;
+    let cipher = Aes::new(&random_bytes(BLOCK_SIZE))?;
+
+    let oracle = |input: &[u8]| {
+        let mut plaintext = input.to_vec();
+        plaintext.extend(&secret);
+        ecb_encrypt(&cipher, &plaintext).unwrap()
+    };
+
+    let recovered = byte_at_a_time_decrypt(oracle)?;
+    Ok(String::from_utf8(recovered)?)
+}
@@ -0,0 +1,16 @@
+use crate::ecb_cut_and_paste::{forge_admin_profile, ProfileService};
+use crate::error::Result;
+
+/// Cut and paste ECB blocks from profiles we are allowed to request to make an admin profile
+///
+pub fn solve() -> Result<String> {
+    let service = ProfileService::new();
+    let forged = forge_admin_profile(|email| service.encrypted_profile_for(email))?;
+
+    let profile = service.decrypt_profile(&forged)?;
+    let pairs: Vec<String> = profile
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    Ok(pairs.join("&"))
+}
@@ -0,0 +1,26 @@
+use crate::aes::{Aes, BLOCK_SIZE};
+use crate::base64_converter::base64_decode;
+use crate::ecb_byte_at_a_time::byte_at_a_time_decrypt;
+use crate::error::Result;
+use crate::modes::ecb_encrypt;
+use crate::random::{random_bytes, random_range};
+use crate::set2::challenge12::SECRET;
+
+/// Decrypt the secret appended to our input by an ECB oracle one byte at a time when the
+/// oracle also prepends a random count of random bytes
+///
+pub fn solve() -> Result<String> {
+    let secret = base64_decode(SECRET)?;
+    let cipher = Aes::new(&random_bytes(BLOCK_SIZE))?;
+    let prefix = random_bytes(random_range(0, 4 * BLOCK_SIZE));
+
+    let oracle = |input: &[u8]| {
+        let mut plaintext = prefix.clone();
+        plaintext.extend(input);
+        plaintext.extend(&secret);
+        ecb_encrypt(&cipher, &plaintext).unwrap()
+    };
+
+    let recovered = byte_at_a_time_decrypt(oracle)?;
+    Ok(String::from_utf8(recovered)?)
+}
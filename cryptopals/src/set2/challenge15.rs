@@ -0,0 +1,18 @@
+use crate::error::{CryptoError, Result};
+use crate::padding::pkcs7_unpad;
+
+/// Validate and strip PKCS#7 padding, rejecting the badly padded strings
+///
+pub fn solve() -> Result<String> {
+    let unpadded = pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x04", 16)?;
+
+    let invalid: [&[u8]; 2] = [
+        b"ICE ICE BABY\x05\x05\x05\x05",
+        b"ICE ICE BABY\x01\x02\x03\x04",
+    ];
+    if invalid.iter().any(|data| pkcs7_unpad(data, 16).is_ok()) {
+        return Err(CryptoError::NoSolution("invalid padding was accepted"));
+    }
+
+    Ok(String::from_utf8(unpadded.to_vec())?)
+}
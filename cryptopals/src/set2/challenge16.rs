@@ -0,0 +1,15 @@
+use crate::bitflipping::{forge_admin_cbc, CommentService};
+use crate::error::{CryptoError, Result};
+
+/// Flip bits in a CBC encrypted cookie to inject ";admin=true;" past the quoting
+///
+pub fn solve() -> Result<String> {
+    let service = CommentService::new();
+    let forged = forge_admin_cbc(&service)?;
+
+    if service.is_admin(&forged)? {
+        Ok("admin=true".to_string())
+    } else {
+        Err(CryptoError::NoSolution("forged cookie is not admin"))
+    }
+}
@@ -1,10 +1,11 @@
+use crate::error::Result;
 use crate::hex_converter::hex_encode;
-use crate::padding::padded;
+use crate::padding::pkcs7_pad;
 
-/// Pad data to a fixed alignment
+/// PKCS#7 pad data to a fixed block size
 ///
-pub fn solve() -> String {
+pub fn solve() -> Result<String> {
     let data = b"YELLOW SUBMARINE";
-    let p = padded(&data[..], 20);
-    hex_encode(&p)
+    let p = pkcs7_pad(&data[..], 20);
+    Ok(hex_encode(&p))
 }
@@ -0,0 +1,32 @@
+use crate::base64_converter::base64_decode;
+use crate::error::Result;
+use crate::padding_oracle::{padding_oracle_decrypt, PaddingOracleService};
+
+pub const STRINGS: [&str; 10] = [
+    "MDAwMDAwTm93IHRoYXQgdGhlIHBhcnR5IGlzIGp1bXBpbmc=",
+    "MDAwMDAxV2l0aCB0aGUgYmFzcyBraWNrZWQgaW4gYW5kIHRoZSBWZWdhJ3MgYXJlIHB1bXBpbic=",
+    "MDAwMDAyUXVpY2sgdG8gdGhlIHBvaW50LCB0byB0aGUgcG9pbnQsIG5vIGZha2luZw==",
+    "MDAwMDAzQ29va2luZyBNQydzIGxpa2UgYSBwb3VuZCBvZiBiYWNvbg==",
+    "MDAwMDA0QnVybmluZyAnZW0sIGlmIHlvdSBhaW4ndCBxdWljayBhbmQgbmltYmxl",
+    "MDAwMDA1SSBnbyBjcmF6eSB3aGVuIEkgaGVhciBhIGN5bWJhbA==",
+    "MDAwMDA2QW5kIGEgaGlnaCBoYXQgd2l0aCBhIHNvdXBlZCB1cCB0ZW1wbw==",
+    "MDAwMDA3SSdtIG9uIGEgcm9sbCwgaXQncyB0aW1lIHRvIGdvIHNvbG8=",
+    "MDAwMDA4b2xsaW4nIGluIG15IGZpdmUgcG9pbnQgb2g=",
+    "MDAwMDA5aXRoIG15IHJhZy10b3AgZG93biBzbyBteSBoYWlyIGNhbiBibG93",
+];
+
+/// Decrypt each of the strings using only whether the service accepts the padding
+///
+pub fn solve() -> Result<String> {
+    let service = PaddingOracleService::new();
+    let oracle = |iv: &[u8], ct: &[u8]| service.padding_valid(iv, ct);
+
+    let mut lines = Vec::with_capacity(STRINGS.len());
+    for s in STRINGS.iter() {
+        let (iv, encrypted) = service.encrypt(&base64_decode(s)?);
+        let decrypted = padding_oracle_decrypt(oracle, &iv, &encrypted)?;
+        lines.push(String::from_utf8(decrypted)?);
+    }
+
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,33 @@
+use crate::base64_converter::base64_decode;
+use crate::error::Result;
+use crate::fixed_nonce_ctr::{encrypt_with_fixed_nonce, Keystream};
+use crate::payloads;
+use crate::random::random_bytes;
+
+/// Break CTR encryption with a fixed nonce statistically, truncating the ciphertexts to the
+/// length of the shortest so the problem becomes repeating key XOR.
+///
+/// The payload is the challenge's 20.txt, one base64 line per plaintext. It isn't in the
+/// repository yet, so the challenge isn't registered until it is added with its answer.
+///
+pub fn solve() -> Result<String> {
+    let payload = payloads::load("set3/challenge20.txt")?;
+    let plaintexts = payload
+        .lines()
+        .map(base64_decode)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let ciphertexts = encrypt_with_fixed_nonce(&random_bytes(16), &plaintexts)?;
+    let refs: Vec<&[u8]> = ciphertexts.iter().map(|c| &c[..]).collect();
+
+    let mut keystream = Keystream::recover_truncated(&refs);
+    let len = keystream.bytes().len();
+
+    //Case isn't distinguishable in the first column, the first line starts "I'm rated"
+    keystream.correct(&ciphertexts[0], 0, b"I");
+
+    let lines = ciphertexts
+        .iter()
+        .map(|c| String::from_utf8(keystream.decrypt(&c[..len])))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,10 @@
+use crate::error::Result;
+use crate::mt19937::Mt19937;
+
+/// The first outputs of MT19937 with the reference default seed
+///
+pub fn solve() -> Result<String> {
+    let mut mt = Mt19937::new(5489);
+    let outputs: Vec<String> = (0..5).map(|_| mt.next_u32().to_string()).collect();
+    Ok(outputs.join(" "))
+}
@@ -0,0 +1,21 @@
+use crate::error::{CryptoError, Result};
+use crate::mt19937::{crack_timestamp_seed, Mt19937};
+use crate::random::random_range;
+
+//When the generator was seeded, fixed so the recovered seed can be checked
+const SEEDED_AT: u32 = 1_539_000_000;
+
+//How far back from now we search for the seed
+const SEARCH_WINDOW: u32 = 3000;
+
+/// Seed an MT19937 with a timestamp, "wait" a random while and then recover the seed from
+/// the first output. Time passing is simulated rather than actually sleeping.
+///
+pub fn solve() -> Result<String> {
+    let output = Mt19937::new(SEEDED_AT).next_u32();
+    let now = SEEDED_AT + random_range(40, 1001) as u32;
+
+    let seed = crack_timestamp_seed(output, now, SEARCH_WINDOW)
+        .ok_or(CryptoError::NoSolution("timestamp seed not found"))?;
+    Ok(seed.to_string())
+}
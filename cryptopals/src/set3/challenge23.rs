@@ -0,0 +1,26 @@
+use crate::error::{CryptoError, Result};
+use crate::mt19937::{clone_mt19937, Mt19937, STATE_SIZE};
+use crate::random::random_u64;
+
+const NUM_PREDICTIONS: usize = 1000;
+
+/// Clone an MT19937 from its outputs by untempering them back into the state, then check
+/// the clone predicts the original's future outputs
+///
+pub fn solve() -> Result<String> {
+    let mut mt = Mt19937::new(random_u64() as u32);
+    let outputs: Vec<u32> = (0..STATE_SIZE).map(|_| mt.next_u32()).collect();
+    let mut clone = clone_mt19937(&outputs)?;
+
+    let predicted = (0..NUM_PREDICTIONS)
+        .filter(|_| clone.next_u32() == mt.next_u32())
+        .count();
+    if predicted != NUM_PREDICTIONS {
+        return Err(CryptoError::NoSolution("clone diverged from the original"));
+    }
+
+    Ok(format!(
+        "Predicted {} of {} outputs",
+        predicted, NUM_PREDICTIONS
+    ))
+}
@@ -0,0 +1,39 @@
+use crate::error::{CryptoError, Result};
+use crate::mt_stream_cipher::{
+    is_timestamp_token, mt_stream_cipher, password_reset_token, recover_stream_seed,
+    token_timestamp, TOKEN_LEN,
+};
+use crate::random::{random_bytes, random_range};
+
+const KNOWN: &[u8] = b"AAAAAAAAAAAAAA";
+
+//The stream cipher key and when the token was issued, fixed so the recovered values can
+//be checked
+const STREAM_SEED: u16 = 0x5eed;
+const TOKEN_ISSUED_AT: u32 = 1_539_000_000;
+
+//How far back from now we check for a timestamp seeded token
+const TOKEN_WINDOW: u32 = 600;
+
+/// Recover the 16-bit seed of the MT19937 stream cipher from a known plaintext suffix, then
+/// recover the timestamp a password reset token was generated from. Returns the seed and
+/// the timestamp.
+///
+pub fn solve() -> Result<String> {
+    let mut plaintext = random_bytes(random_range(0, 64));
+    plaintext.extend(KNOWN);
+    let encrypted = mt_stream_cipher(STREAM_SEED, &plaintext);
+    let seed = recover_stream_seed(&encrypted, KNOWN)?;
+
+    let now = TOKEN_ISSUED_AT + random_range(0, 300) as u32;
+    let token = password_reset_token(TOKEN_ISSUED_AT);
+    if is_timestamp_token(&random_bytes(TOKEN_LEN), now, TOKEN_WINDOW) {
+        return Err(CryptoError::NoSolution(
+            "random token mistaken for a timestamp token",
+        ));
+    }
+    let timestamp = token_timestamp(&token, now, TOKEN_WINDOW)
+        .ok_or(CryptoError::NoSolution("token is not timestamp seeded"))?;
+
+    Ok(format!("{} {}", seed, timestamp))
+}
@@ -0,0 +1,24 @@
+use crate::aes::Aes;
+use crate::base64_converter::base64_decode;
+use crate::ctr_edit::{recover_with_edit, EditService};
+use crate::error::Result;
+use crate::modes::ecb_decrypt;
+use crate::payloads;
+
+/// Recover a CTR encrypted message when we are given an edit function that seeks into the
+/// ciphertext and re-encrypts new plaintext there. The plaintext is that of challenge 7.
+///
+pub fn solve() -> Result<String> {
+    let encoded = payloads::load("set1/challenge7.txt")?;
+    let cipher = Aes::new(b"YELLOW SUBMARINE")?;
+    let plaintext = ecb_decrypt(&cipher, &base64_decode(&encoded)?)?;
+
+    let service = EditService::new();
+    let encrypted = service.encrypt(&plaintext)?;
+    let recovered = recover_with_edit(
+        |ciphertext, offset, newtext| service.edit(ciphertext, offset, newtext),
+        &encrypted,
+    )?;
+
+    Ok(String::from_utf8(recovered)?)
+}
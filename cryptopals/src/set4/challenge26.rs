@@ -0,0 +1,15 @@
+use crate::bitflipping::{forge_admin_ctr, CommentMode, CommentService};
+use crate::error::{CryptoError, Result};
+
+/// Flip bits in a CTR encrypted cookie to inject ";admin=true;" past the quoting
+///
+pub fn solve() -> Result<String> {
+    let service = CommentService::with_mode(CommentMode::Ctr);
+    let forged = forge_admin_ctr(&service)?;
+
+    if service.is_admin(&forged)? {
+        Ok("admin=true".to_string())
+    } else {
+        Err(CryptoError::NoSolution("forged cookie is not admin"))
+    }
+}
@@ -0,0 +1,27 @@
+use crate::aes::BLOCK_SIZE;
+use crate::cbc_key_as_iv::{recover_key_as_iv, KeyAsIvService};
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+
+const MESSAGE: &[u8] =
+    b"comment1=cooking%20MCs;userdata=;comment2=%20like%20a%20pound%20of%20bacon";
+
+//The FIPS-197 example key, fixed so the recovered key can be checked
+const KEY: [u8; BLOCK_SIZE] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+/// Recover the key from a CBC service that uses its key as the IV, using the plaintext it
+/// leaks when it rejects a message with high-ASCII
+///
+pub fn solve() -> Result<String> {
+    let service = KeyAsIvService::with_key(KEY);
+    let encrypted = service.encrypt(MESSAGE);
+    let key = recover_key_as_iv(|ciphertext| service.check_ascii(ciphertext), &encrypted)?;
+
+    if service.is_key(&key) {
+        Ok(hex_encode(&key))
+    } else {
+        Err(CryptoError::NoSolution("recovered the wrong key"))
+    }
+}
@@ -0,0 +1,24 @@
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::length_extension::secret_prefix_mac;
+use crate::sha1::Sha1;
+
+pub const MESSAGE: &[u8] =
+    b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+/// SHA-1 keyed MAC of the message, checking that changing the message or the key changes it
+///
+pub fn solve() -> Result<String> {
+    let key = b"YELLOW SUBMARINE";
+    let mac = secret_prefix_mac::<Sha1>(key, MESSAGE);
+
+    let mut tampered = MESSAGE.to_vec();
+    tampered[0] ^= 1;
+    if secret_prefix_mac::<Sha1>(key, &tampered) == mac
+        || secret_prefix_mac::<Sha1>(b"", MESSAGE) == mac
+    {
+        return Err(CryptoError::NoSolution("MAC did not change"));
+    }
+
+    Ok(hex_encode(&mac))
+}
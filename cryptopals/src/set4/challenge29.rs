@@ -0,0 +1,32 @@
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::length_extension::{forge_extension, MacService};
+use crate::set4::challenge28::MESSAGE;
+use crate::sha1::Sha1;
+
+const EXTENSION: &[u8] = b";admin=true";
+const MAX_KEY_LEN: usize = 64;
+
+//The key is unknown to the attack, but fixed so the forged MAC can be checked
+const KEY: &[u8] = b"squeamish ossifrage";
+
+/// Forge a SHA-1 keyed MAC for the message extended with ";admin=true" without knowing the
+/// key or its length
+///
+pub fn solve() -> Result<String> {
+    let service = MacService::<Sha1>::with_key(KEY);
+    let mac = service.sign(MESSAGE);
+
+    let (forged, forged_mac) = forge_extension::<Sha1, _>(
+        |message, mac| service.verify(message, mac),
+        MESSAGE,
+        &mac,
+        EXTENSION,
+        MAX_KEY_LEN,
+    )?;
+    if !forged.ends_with(EXTENSION) || !service.verify(&forged, &forged_mac) {
+        return Err(CryptoError::NoSolution("forged MAC was not accepted"));
+    }
+
+    Ok(hex_encode(&forged_mac))
+}
@@ -0,0 +1,51 @@
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::sha1::DIGEST_LEN;
+use crate::timing_leak::{recover_mac, timed_request, HmacServer, TimingAttack};
+use std::time::Duration;
+
+const FILE: &str = "foo";
+
+//The server's HMAC key, fixed so the recovered HMAC can be checked
+const KEY: &[u8] = b"YELLOW SUBMARINE";
+
+//The challenge sleeps 50ms per byte, which would take hours to break. The leak is scaled
+//down so it breaks in a minute or so, while still standing well clear of the noise.
+const DELAY: Duration = Duration::from_micros(250);
+
+//The odd request oversleeps by more than the leak, so even here one sample isn't enough
+const ATTACK: TimingAttack = TimingAttack {
+    samples: 3,
+    finalists: 5,
+    confirm_samples: 5,
+};
+
+/// Recover a valid HMAC-SHA1 for a file from a local server that leaks how many leading
+/// bytes of the signature are right through an early-exit comparison with a sleep per byte
+///
+/// This deviates from the challenge, which sleeps 50ms per byte. The server sleeps 250µs so
+/// the whole 20 byte HMAC can be recovered in a few minutes. timing_leak's tests run the
+/// attack against a real 5ms leak on a short MAC.
+///
+pub fn solve() -> Result<String> {
+    break_server(DELAY, &ATTACK)
+}
+
+/// Start a server with the given leak, then recover a signature it accepts for the file
+///
+pub fn break_server(delay: Duration, attack: &TimingAttack) -> Result<String> {
+    let server = HmacServer::start(KEY, delay)?;
+    let addr = server.addr();
+    let mac = recover_mac(
+        |signature| Ok(timed_request(addr, FILE, signature)?),
+        DIGEST_LEN,
+        attack,
+    )?;
+
+    if !timed_request(addr, FILE, &mac)?.0 {
+        return Err(CryptoError::NoSolution(
+            "server rejected the recovered HMAC",
+        ));
+    }
+    Ok(hex_encode(&mac))
+}
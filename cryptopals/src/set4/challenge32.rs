@@ -0,0 +1,24 @@
+use crate::error::Result;
+use crate::set4::challenge31::break_server;
+use crate::timing_leak::TimingAttack;
+use std::time::Duration;
+
+//The challenge's 5ms scaled down like challenge 31's leak. At this size the leak is lost
+//in the noise of a request over loopback without repeated samples.
+const DELAY: Duration = Duration::from_micros(50);
+
+const ATTACK: TimingAttack = TimingAttack {
+    samples: 7,
+    finalists: 5,
+    confirm_samples: 21,
+};
+
+/// The same attack with a leak small enough that it takes repeated samples per candidate
+///
+/// This deviates from the challenge, which sleeps 5ms per byte. The server sleeps 50µs, a
+/// smaller leak relative to the noise than the challenge's, so the whole HMAC can be
+/// recovered in a few minutes.
+///
+pub fn solve() -> Result<String> {
+    break_server(DELAY, &ATTACK)
+}
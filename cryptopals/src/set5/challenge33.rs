@@ -0,0 +1,33 @@
+use crate::biguint::BigUint;
+use crate::dh::{derive_key, DhGroup, KeyPair};
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::sha256::Sha256;
+
+//Private keys for the NIST group, fixed so the session key can be checked
+const A_PRIVATE: u64 = 0x0123_4567_89ab_cdef;
+const B_PRIVATE: u64 = 0xfedc_ba98_7654_3210;
+
+/// Agree on a session key with Diffie-Hellman, first in a toy group with random keys then in
+/// the NIST group. Returns the NIST group session key.
+///
+pub fn solve() -> Result<String> {
+    let toy = DhGroup::new(BigUint::from_u64(37), BigUint::from_u64(5));
+    let (a, b) = (toy.generate_keypair(), toy.generate_keypair());
+    agreed_key(&toy, &a, &b)?;
+
+    let nist = DhGroup::nist();
+    let a = nist.keypair(BigUint::from_u64(A_PRIVATE));
+    let b = nist.keypair(BigUint::from_u64(B_PRIVATE));
+    Ok(hex_encode(&agreed_key(&nist, &a, &b)?))
+}
+
+//The session key both sides derive, as long as they agree on it
+fn agreed_key(group: &DhGroup, a: &KeyPair, b: &KeyPair) -> Result<Vec<u8>> {
+    let key_a = derive_key::<Sha256>(&a.shared_secret(group, b.public()));
+    let key_b = derive_key::<Sha256>(&b.shared_secret(group, a.public()));
+    if key_a != key_b {
+        return Err(CryptoError::NoSolution("session keys differ"));
+    }
+    Ok(key_a)
+}
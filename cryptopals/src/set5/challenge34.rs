@@ -0,0 +1,21 @@
+use crate::dh::DhGroup;
+use crate::dh_mitm::{run_echo, Initiator, KeyFixingMitm, Responder};
+use crate::error::{CryptoError, Result};
+use crate::sha1::Sha1;
+
+pub const MESSAGE: &[u8] = b"Meet me at the usual place";
+
+/// Sit between A and B swapping both public keys for p, which fixes the shared secret at
+/// zero, and read the message as it is echoed
+///
+pub fn solve() -> Result<String> {
+    let mut a = Initiator::<Sha1>::new(DhGroup::nist(), MESSAGE);
+    let mut b = Responder::new();
+    let mut mitm = KeyFixingMitm::<Sha1>::new();
+    run_echo(&mut a, &mut b, &mut mitm)?;
+
+    match mitm.recovered() {
+        [sent, echoed] if sent == echoed => Ok(String::from_utf8(sent.clone())?),
+        _ => Err(CryptoError::NoSolution("messages were not recovered")),
+    }
+}
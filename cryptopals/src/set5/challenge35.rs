@@ -0,0 +1,28 @@
+use crate::dh::DhGroup;
+use crate::dh_mitm::{run_echo, GeneratorMitm, Initiator, MaliciousGenerator, Responder};
+use crate::error::{CryptoError, Result};
+use crate::set5::challenge34::MESSAGE;
+use crate::sha1::Sha1;
+
+/// Sit between A and B replacing the negotiated generator with 1, p and p - 1 in turn, and
+/// read the message each time from the secret those generators force
+///
+pub fn solve() -> Result<String> {
+    let generators = [
+        MaliciousGenerator::One,
+        MaliciousGenerator::P,
+        MaliciousGenerator::PMinusOne,
+    ];
+    for &generator in &generators {
+        let mut a = Initiator::<Sha1>::new(DhGroup::nist(), MESSAGE);
+        let mut b = Responder::new();
+        let mut mitm = GeneratorMitm::<Sha1>::new(generator);
+        run_echo(&mut a, &mut b, &mut mitm)?;
+
+        if mitm.recovered() != [MESSAGE, MESSAGE] {
+            return Err(CryptoError::NoSolution("messages were not recovered"));
+        }
+    }
+
+    Ok(String::from_utf8(MESSAGE.to_vec())?)
+}
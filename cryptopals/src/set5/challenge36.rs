@@ -0,0 +1,40 @@
+use crate::biguint::BigUint;
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::srp::{key_proof, login, SrpClient, SrpParams, SrpServer};
+
+pub const EMAIL: &str = "user@example.com";
+pub const PASSWORD: &[u8] = b"correct horse battery staple";
+
+//The salt and both private keys, fixed so the session key can be checked
+const SALT: &[u8] = b"NaCl";
+const A_PRIVATE: u64 = 0x0123_4567_89ab_cdef;
+const B_PRIVATE: u64 = 0xfedc_ba98_7654_3210;
+
+/// Log in to an SRP server with the right password, and fail to with the wrong one.
+/// Returns the session key of the successful login.
+///
+pub fn solve() -> Result<String> {
+    let params = SrpParams::nist();
+    let mut server = SrpServer::with_salt(params.clone(), EMAIL, PASSWORD, SALT);
+
+    let client = SrpClient::with_private(
+        params.clone(),
+        EMAIL,
+        PASSWORD,
+        BigUint::from_u64(A_PRIVATE),
+    );
+    let (salt, b_public) =
+        server.hello_with_private(EMAIL, client.public(), &BigUint::from_u64(B_PRIVATE))?;
+    let key = client.session_key(&salt, &b_public);
+    if !server.verify(&key_proof(&key, &salt))? {
+        return Err(CryptoError::NoSolution("the right password was rejected"));
+    }
+
+    let client = SrpClient::new(params, EMAIL, b"incorrect horse battery staple");
+    if login(&client, &mut server)? {
+        return Err(CryptoError::NoSolution("the wrong password was accepted"));
+    }
+
+    Ok(hex_encode(&key))
+}
@@ -0,0 +1,17 @@
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::hex_encode;
+use crate::set5::challenge36::{EMAIL, PASSWORD};
+use crate::srp::{zero_key_login, SrpParams, SrpServer};
+
+/// Log in to the SRP server without the password by sending 0, N and 2N as the public key.
+/// Returns the session key the server accepted.
+///
+pub fn solve() -> Result<String> {
+    let mut server = SrpServer::new(SrpParams::nist(), EMAIL, PASSWORD);
+    let mut key = Vec::new();
+    for multiple in 0..3 {
+        key = zero_key_login(&mut server, EMAIL, multiple)?
+            .ok_or(CryptoError::NoSolution("zero key login was rejected"))?;
+    }
+    Ok(hex_encode(&key))
+}
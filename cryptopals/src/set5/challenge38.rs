@@ -0,0 +1,38 @@
+use crate::error::{CryptoError, Result};
+use crate::payloads;
+use crate::set5::challenge36::EMAIL;
+use crate::simplified_srp::{DictionaryMitm, SimpleSrpClient};
+use crate::srp::SrpParams;
+
+/// Name of the wordlist payload, which can be replaced with --wordlist
+///
+pub const WORDLIST: &str = "set5/challenge38.txt";
+
+/// The client's password, fixed so the cracked password can be checked. A replacement
+/// wordlist needs to include it.
+///
+pub const PASSWORD: &str = "trustno1";
+
+/// Pose as a simplified SRP server to a client, and crack its password offline from the
+/// proof it sends by trying each word in the wordlist
+///
+/// The payload is one password per line, by default a short list of common passwords.
+///
+pub fn solve() -> Result<String> {
+    let payload = payloads::load(WORDLIST)?;
+    let words: Vec<&str> = payload
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let params = SrpParams::nist();
+    let client = SimpleSrpClient::new(params.clone(), EMAIL, PASSWORD.as_bytes());
+    let mut mitm = DictionaryMitm::new(params);
+    let challenge = mitm.hello(client.public());
+    mitm.verify(&client.proof(&challenge));
+
+    mitm.crack(words.iter().copied())
+        .map(str::to_string)
+        .ok_or(CryptoError::NoSolution("password is not in the wordlist"))
+}
@@ -0,0 +1,23 @@
+use crate::biguint::BigUint;
+use crate::error::{CryptoError, Result};
+use crate::rsa::{RsaPrivateKey, RsaPublicKey};
+
+pub const MODULUS_BITS: usize = 1024;
+pub const MESSAGE: &[u8] = b"Textbook RSA";
+
+/// Implement RSA with e = 3 and round trip a message through it, and through the PEM
+/// encodings of the keys
+///
+pub fn solve() -> Result<String> {
+    //The worked example, 17 * 2753 = 1 mod 3120
+    if BigUint::from_u64(17).modinv(&BigUint::from_u64(3120)) != Some(BigUint::from_u64(2753)) {
+        return Err(CryptoError::NoSolution("modular inverse is wrong"));
+    }
+
+    let key = RsaPrivateKey::from_pem(&RsaPrivateKey::generate(MODULUS_BITS, 3)?.to_pem()?)?;
+    let public = RsaPublicKey::from_pem(&key.public_key().to_pem())?;
+
+    let ciphertext = public.encrypt(&BigUint::from_bytes_be(MESSAGE))?;
+    let plaintext = key.decrypt(&ciphertext)?;
+    Ok(String::from_utf8(plaintext.to_bytes_be())?)
+}
@@ -0,0 +1,22 @@
+use crate::biguint::BigUint;
+use crate::error::Result;
+use crate::rsa::RsaPrivateKey;
+use crate::rsa_broadcast::broadcast_attack;
+use crate::set5::challenge39::MODULUS_BITS;
+
+const MESSAGE: &[u8] = b"The same message to three recipients";
+
+/// Recover a message encrypted under three different e = 3 public keys, by combining the
+/// ciphertexts with the Chinese remainder theorem and taking a cube root
+///
+pub fn solve() -> Result<String> {
+    let message = BigUint::from_bytes_be(MESSAGE);
+    let mut ciphertexts = Vec::new();
+    for _ in 0..3 {
+        let key = RsaPrivateKey::generate(MODULUS_BITS, 3)?.public_key();
+        ciphertexts.push((key.encrypt(&message)?, key));
+    }
+
+    let recovered = broadcast_attack(&ciphertexts)?;
+    Ok(String::from_utf8(recovered.to_bytes_be())?)
+}
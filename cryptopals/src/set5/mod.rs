@@ -0,0 +1,8 @@
+pub mod challenge33;
+pub mod challenge34;
+pub mod challenge35;
+pub mod challenge36;
+pub mod challenge37;
+pub mod challenge38;
+pub mod challenge39;
+pub mod challenge40;
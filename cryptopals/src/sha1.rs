@@ -0,0 +1,189 @@
+use std::convert::TryInto;
+
+/// Size of the blocks the compression function works on, in bytes
+///
+pub const BLOCK_LEN: usize = 64;
+
+/// Size of the digest in bytes
+///
+pub const DIGEST_LEN: usize = 20;
+
+const INITIAL_STATE: [u32; 5] = [
+    0x6745_2301,
+    0xefcd_ab89,
+    0x98ba_dcfe,
+    0x1032_5476,
+    0xc3d2_e1f0,
+];
+
+/// SHA-1 (FIPS 180-4). The state can be set directly so that hashing can carry on from a
+/// published digest, which is what a length extension attack needs.
+///
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: Vec<u8>,
+    //Total bytes processed, including any the state was resumed from
+    length: u64,
+}
+
+impl Sha1 {
+    pub fn new() -> Sha1 {
+        Sha1::from_state(INITIAL_STATE, 0)
+    }
+
+    /// Resume hashing from an internal state after length bytes have been processed. The
+    /// length must be a whole number of blocks (including the padding of the original message).
+    ///
+    pub fn from_state(state: [u32; 5], length: u64) -> Sha1 {
+        Sha1 {
+            state,
+            buffer: Vec::with_capacity(BLOCK_LEN),
+            length,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend(data);
+        let whole = self.buffer.len() - self.buffer.len() % BLOCK_LEN;
+        for block in self.buffer[..whole].chunks(BLOCK_LEN) {
+            compress(&mut self.state, block);
+        }
+        self.buffer.drain(..whole);
+    }
+
+    pub fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let padding = sha1_padding(self.length);
+        self.update(&padding);
+
+        let mut digest = [0u8; DIGEST_LEN];
+        for (chunk, word) in digest.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHA-1 digest of the data
+///
+pub fn sha1(data: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The internal state that produced a digest
+///
+pub fn state_from_digest(digest: &[u8; DIGEST_LEN]) -> [u32; 5] {
+    let mut state = [0u32; 5];
+    for (word, chunk) in state.iter_mut().zip(digest.chunks(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    state
+}
+
+/// The padding SHA-1 appends to a message of the given length: a 1 bit, zeros up to 56 bytes
+/// into the last block, then the length in bits as a big-endian u64
+///
+pub fn sha1_padding(message_len: u64) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    while (message_len as usize + padding.len()) % BLOCK_LEN != BLOCK_LEN - 8 {
+        padding.push(0);
+    }
+    padding.extend(&(message_len.wrapping_mul(8)).to_be_bytes());
+    padding
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+    for (word, chunk) in w.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+            20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e].iter()) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::hex_converter::hex_encode;
+
+#[test]
+fn test_sha1_fips_180() {
+    assert_eq!(
+        hex_encode(&sha1(b"abc")),
+        "A9993E364706816ABA3E25717850C26C9CD0D89D"
+    );
+    assert_eq!(
+        hex_encode(&sha1(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "84983E441C3BD26EBAAE4AA1F95129E5E54670F1"
+    );
+    assert_eq!(
+        hex_encode(&sha1(&vec![b'a'; 1_000_000])),
+        "34AA973CD4C4DAA4F61EEB2BDBAD27316534016F"
+    );
+    assert_eq!(
+        hex_encode(&sha1(b"")),
+        "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709"
+    );
+}
+
+#[test]
+fn test_sha1_incremental() {
+    let data: Vec<u8> = (0..200u8).collect();
+    let mut hasher = Sha1::new();
+    for chunk in data.chunks(7) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.finalize(), sha1(&data));
+}
+
+#[test]
+fn test_sha1_resume_from_state() {
+    let message = b"The quick brown fox";
+    let mut glued = message.to_vec();
+    glued.extend(sha1_padding(message.len() as u64));
+    glued.extend(b" jumps over the lazy dog");
+
+    let mut resumed = Sha1::from_state(
+        state_from_digest(&sha1(message)),
+        (message.len() + sha1_padding(message.len() as u64).len()) as u64,
+    );
+    resumed.update(b" jumps over the lazy dog");
+    assert_eq!(resumed.finalize(), sha1(&glued));
+}
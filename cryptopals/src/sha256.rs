@@ -0,0 +1,247 @@
+use std::convert::TryInto;
+
+/// Size of the blocks the compression function works on, in bytes
+///
+pub const BLOCK_LEN: usize = 64;
+
+/// Size of the digest in bytes
+///
+pub const DIGEST_LEN: usize = 32;
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+//First 32 bits of the fractional parts of the cube roots of the first 64 primes
+const K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+/// SHA-256 (FIPS 180-4), with the same resumable state as SHA-1
+///
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    //Total bytes processed, including any the state was resumed from
+    length: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Sha256 {
+        Sha256::from_state(INITIAL_STATE, 0)
+    }
+
+    /// Resume hashing from an internal state after length bytes have been processed. The
+    /// length must be a whole number of blocks (including the padding of the original message).
+    ///
+    pub fn from_state(state: [u32; 8], length: u64) -> Sha256 {
+        Sha256 {
+            state,
+            buffer: Vec::with_capacity(BLOCK_LEN),
+            length,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend(data);
+        let whole = self.buffer.len() - self.buffer.len() % BLOCK_LEN;
+        for block in self.buffer[..whole].chunks(BLOCK_LEN) {
+            compress(&mut self.state, block);
+        }
+        self.buffer.drain(..whole);
+    }
+
+    pub fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let padding = sha256_padding(self.length);
+        self.update(&padding);
+
+        let mut digest = [0u8; DIGEST_LEN];
+        for (chunk, word) in digest.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHA-256 digest of the data
+///
+pub fn sha256(data: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The internal state that produced a digest
+///
+pub fn state_from_digest(digest: &[u8; DIGEST_LEN]) -> [u32; 8] {
+    let mut state = [0u32; 8];
+    for (word, chunk) in state.iter_mut().zip(digest.chunks(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    state
+}
+
+/// The padding SHA-256 appends to a message of the given length, the same as SHA-1's
+///
+pub fn sha256_padding(message_len: u64) -> Vec<u8> {
+    crate::sha1::sha1_padding(message_len)
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (word, chunk) in w.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (word, k) in w.iter().zip(K.iter()) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(*k)
+            .wrapping_add(*word);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::hex_converter::hex_encode;
+
+#[test]
+fn test_sha256_fips_180() {
+    assert_eq!(
+        hex_encode(&sha256(b"abc")),
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+    );
+    assert_eq!(
+        hex_encode(&sha256(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248D6A61D20638B8E5C026930C3E6039A33CE45964FF2167F6ECEDD419DB06C1"
+    );
+    assert_eq!(
+        hex_encode(&sha256(b"")),
+        "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+    );
+}
+
+#[test]
+fn test_sha256_resume_from_state() {
+    let message = b"The quick brown fox";
+    let padding = sha256_padding(message.len() as u64);
+    let mut glued = message.to_vec();
+    glued.extend(&padding);
+    glued.extend(b" jumps over the lazy dog");
+
+    let mut resumed = Sha256::from_state(
+        state_from_digest(&sha256(message)),
+        (message.len() + padding.len()) as u64,
+    );
+    resumed.update(b" jumps over the lazy dog");
+    assert_eq!(resumed.finalize(), sha256(&glued));
+}
@@ -0,0 +1,196 @@
+use crate::biguint::BigUint;
+use crate::random::random_bytes;
+use crate::srp::{key_proof, private_key, session_key, SrpError, SrpParams};
+
+const SALT_LEN: usize = 16;
+
+//The scrambling parameter is a random 128-bit number rather than a hash of the public keys
+const SCRAMBLER_LEN: usize = 16;
+
+/// What the server sends back to a client starting a login
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerChallenge {
+    pub salt: Vec<u8>,
+    pub b_public: BigUint,
+    pub u: BigUint,
+}
+
+/// Simplified SRP drops the verifier from B, so B = g^b and the client's secret is
+/// B^(a + ux). Nothing in it depends on the verifier, so a fake server can make everything
+/// but x known and test password guesses offline.
+///
+pub struct SimpleSrpServer {
+    params: SrpParams,
+    email: String,
+    salt: Vec<u8>,
+    verifier: BigUint,
+    session_key: Option<Vec<u8>>,
+}
+
+impl SimpleSrpServer {
+    pub fn new(params: SrpParams, email: &str, password: &[u8]) -> SimpleSrpServer {
+        let salt = random_bytes(SALT_LEN);
+        let verifier = params.g.modpow(&private_key(&salt, password), &params.n);
+        SimpleSrpServer {
+            params,
+            email: email.to_string(),
+            salt,
+            verifier,
+            session_key: None,
+        }
+    }
+
+    pub fn hello(
+        &mut self,
+        email: &str,
+        a_public: &BigUint,
+    ) -> std::result::Result<ServerChallenge, SrpError> {
+        if email != self.email {
+            return Err(SrpError::UnknownUser);
+        }
+
+        let n = &self.params.n;
+        let b = BigUint::random_range(&BigUint::one(), n);
+        let u = BigUint::from_bytes_be(&random_bytes(SCRAMBLER_LEN));
+
+        //S = (A * v^u)^b
+        let base = &(a_public * &self.verifier.modpow(&u, n)) % n;
+        self.session_key = Some(session_key(&base.modpow(&b, n)));
+        Ok(ServerChallenge {
+            salt: self.salt.clone(),
+            b_public: self.params.g.modpow(&b, n),
+            u,
+        })
+    }
+
+    /// Check the client's proof of the session key, which ends the login either way
+    ///
+    pub fn verify(&mut self, proof: &[u8]) -> std::result::Result<bool, SrpError> {
+        let key = self.session_key.take().ok_or(SrpError::NoSession)?;
+        Ok(key_proof(&key, &self.salt) == proof)
+    }
+}
+
+/// A user logging in to a simplified SRP server with their password
+///
+pub struct SimpleSrpClient {
+    params: SrpParams,
+    email: String,
+    password: Vec<u8>,
+    private: BigUint,
+    public: BigUint,
+}
+
+impl SimpleSrpClient {
+    pub fn new(params: SrpParams, email: &str, password: &[u8]) -> SimpleSrpClient {
+        let private = BigUint::random_range(&BigUint::one(), &params.n);
+        let public = params.g.modpow(&private, &params.n);
+        SimpleSrpClient {
+            params,
+            email: email.to_string(),
+            password: password.to_vec(),
+            private,
+            public,
+        }
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn public(&self) -> &BigUint {
+        &self.public
+    }
+
+    /// The proof of the session key, with S = B^(a + ux)
+    ///
+    pub fn proof(&self, challenge: &ServerChallenge) -> Vec<u8> {
+        let x = private_key(&challenge.salt, &self.password);
+        let exponent = &self.private + &(&challenge.u * &x);
+        let secret = challenge.b_public.modpow(&exponent, &self.params.n);
+        key_proof(&session_key(&secret), &challenge.salt)
+    }
+}
+
+/// Poses as the server and hands out b = 1, u = 1 and an empty salt. The client's secret is
+/// then g^(a + x) = A * g^x, so its proof can be checked against each password guess offline.
+///
+pub struct DictionaryMitm {
+    params: SrpParams,
+    a_public: Option<BigUint>,
+    proof: Option<Vec<u8>>,
+}
+
+impl DictionaryMitm {
+    pub fn new(params: SrpParams) -> DictionaryMitm {
+        DictionaryMitm {
+            params,
+            a_public: None,
+            proof: None,
+        }
+    }
+
+    pub fn hello(&mut self, a_public: &BigUint) -> ServerChallenge {
+        self.a_public = Some(a_public.clone());
+        ServerChallenge {
+            salt: Vec::new(),
+            b_public: self.params.g.clone(),
+            u: BigUint::one(),
+        }
+    }
+
+    /// Keep the client's proof for cracking. The client is told the login failed.
+    ///
+    pub fn verify(&mut self, proof: &[u8]) -> bool {
+        self.proof = Some(proof.to_vec());
+        false
+    }
+
+    /// The first password in the list that produces the captured proof
+    ///
+    pub fn crack<'a, I: IntoIterator<Item = &'a str>>(&self, passwords: I) -> Option<&'a str> {
+        let a_public = self.a_public.as_ref()?;
+        let proof = self.proof.as_ref()?;
+        let n = &self.params.n;
+        passwords.into_iter().find(|password| {
+            let g_x = self
+                .params
+                .g
+                .modpow(&private_key(&[], password.as_bytes()), n);
+            let secret = &(a_public * &g_x) % n;
+            key_proof(&session_key(&secret), &[]) == *proof
+        })
+    }
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+const EMAIL: &str = "user@example.com";
+
+#[test]
+fn test_simplified_srp_login() {
+    let params = SrpParams::nist();
+    let mut server = SimpleSrpServer::new(params.clone(), EMAIL, b"hunter2");
+    for &(password, accepted) in &[(&b"hunter2"[..], true), (&b"hunter3"[..], false)] {
+        let client = SimpleSrpClient::new(params.clone(), EMAIL, password);
+        let challenge = server.hello(client.email(), client.public()).unwrap();
+        assert_eq!(server.verify(&client.proof(&challenge)), Ok(accepted));
+    }
+}
+
+#[test]
+fn test_simplified_srp_dictionary_attack() {
+    let params = SrpParams::nist();
+    let client = SimpleSrpClient::new(params.clone(), EMAIL, b"letmein");
+    let mut mitm = DictionaryMitm::new(params);
+    let challenge = mitm.hello(client.public());
+    assert!(!mitm.verify(&client.proof(&challenge)));
+
+    assert_eq!(
+        mitm.crack(vec!["password", "123456", "letmein", "qwerty"]),
+        Some("letmein")
+    );
+    assert_eq!(mitm.crack(vec!["password", "123456"]), None);
+}
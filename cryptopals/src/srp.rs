@@ -0,0 +1,276 @@
+use crate::biguint::BigUint;
+use crate::dh::{NIST_GENERATOR, NIST_PRIME};
+use crate::hmac::hmac;
+use crate::random::random_bytes;
+use crate::sha256::{sha256, Sha256};
+use std::error::Error;
+use std::fmt;
+
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SrpError {
+    UnknownUser,
+    NoSession,
+}
+
+impl Error for SrpError {
+    fn description(&self) -> &str {
+        match *self {
+            SrpError::UnknownUser => "No such user",
+            SrpError::NoSession => "No login is in progress",
+        }
+    }
+}
+
+impl fmt::Display for SrpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SrpError::UnknownUser => write!(f, "No such user"),
+            SrpError::NoSession => write!(f, "No login is in progress"),
+        }
+    }
+}
+
+/// The group and multiplier both sides agree on
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrpParams {
+    pub n: BigUint,
+    pub g: BigUint,
+    pub k: BigUint,
+}
+
+impl SrpParams {
+    /// The NIST prime and generator 2, with the SRP-6a multiplier k = H(N || PAD(g))
+    ///
+    pub fn nist() -> SrpParams {
+        let n = BigUint::from_hex(NIST_PRIME).unwrap();
+        let g = BigUint::from_u64(NIST_GENERATOR);
+        let k = hash_to_int(&[&n.to_bytes_be(), &pad(&n, &g)]);
+        SrpParams { n, g, k }
+    }
+}
+
+//Left pad to the length of N, as SRP-6a hashes group elements
+fn pad(n: &BigUint, value: &BigUint) -> Vec<u8> {
+    value.to_bytes_be_padded(n.bits().div_ceil(8))
+}
+
+/// SHA-256 of the concatenated parts as a big-endian integer
+///
+pub fn hash_to_int(parts: &[&[u8]]) -> BigUint {
+    BigUint::from_bytes_be(&sha256(&parts.concat()))
+}
+
+/// The private key derived from the password, x = H(salt || password)
+///
+pub fn private_key(salt: &[u8], password: &[u8]) -> BigUint {
+    hash_to_int(&[salt, password])
+}
+
+/// The session key from the shared secret, K = H(S)
+///
+pub fn session_key(secret: &BigUint) -> Vec<u8> {
+    sha256(&secret.to_bytes_be()).to_vec()
+}
+
+/// What the client sends to prove it has the session key, HMAC-SHA256(K, salt)
+///
+pub fn key_proof(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    hmac::<Sha256>(key, salt)
+}
+
+/// The scrambling parameter u = H(PAD(A) || PAD(B))
+///
+fn scrambler(params: &SrpParams, a_public: &BigUint, b_public: &BigUint) -> BigUint {
+    hash_to_int(&[&pad(&params.n, a_public), &pad(&params.n, b_public)])
+}
+
+/// Holds the verifier v = g^x for a single user. It trusts the client's public key, so a
+/// key that is a multiple of N makes the shared secret zero whatever the password.
+///
+pub struct SrpServer {
+    params: SrpParams,
+    email: String,
+    salt: Vec<u8>,
+    verifier: BigUint,
+    session_key: Option<Vec<u8>>,
+}
+
+impl SrpServer {
+    pub fn new(params: SrpParams, email: &str, password: &[u8]) -> SrpServer {
+        SrpServer::with_salt(params, email, password, &random_bytes(SALT_LEN))
+    }
+
+    pub fn with_salt(params: SrpParams, email: &str, password: &[u8], salt: &[u8]) -> SrpServer {
+        let verifier = params.g.modpow(&private_key(salt, password), &params.n);
+        SrpServer {
+            params,
+            email: email.to_string(),
+            salt: salt.to_vec(),
+            verifier,
+            session_key: None,
+        }
+    }
+
+    /// Start a login from the client's email and public key A, returning the salt and the
+    /// server's public key B = kv + g^b
+    ///
+    pub fn hello(
+        &mut self,
+        email: &str,
+        a_public: &BigUint,
+    ) -> std::result::Result<(Vec<u8>, BigUint), SrpError> {
+        let b = BigUint::random_range(&BigUint::one(), &self.params.n);
+        self.hello_with_private(email, a_public, &b)
+    }
+
+    /// Start a login using a chosen private key b rather than a random one
+    ///
+    pub fn hello_with_private(
+        &mut self,
+        email: &str,
+        a_public: &BigUint,
+        b: &BigUint,
+    ) -> std::result::Result<(Vec<u8>, BigUint), SrpError> {
+        if email != self.email {
+            return Err(SrpError::UnknownUser);
+        }
+
+        let n = &self.params.n;
+        let b_public = &(&(&self.params.k * &self.verifier) + &self.params.g.modpow(b, n)) % n;
+
+        //S = (A * v^u)^b
+        let u = scrambler(&self.params, a_public, &b_public);
+        let base = &(a_public * &self.verifier.modpow(&u, n)) % n;
+        self.session_key = Some(session_key(&base.modpow(b, n)));
+        Ok((self.salt.clone(), b_public))
+    }
+
+    /// Check the client's proof of the session key, which ends the login either way
+    ///
+    pub fn verify(&mut self, proof: &[u8]) -> std::result::Result<bool, SrpError> {
+        let key = self.session_key.take().ok_or(SrpError::NoSession)?;
+        Ok(key_proof(&key, &self.salt) == proof)
+    }
+}
+
+/// A user logging in with their password
+///
+pub struct SrpClient {
+    params: SrpParams,
+    email: String,
+    password: Vec<u8>,
+    private: BigUint,
+    public: BigUint,
+}
+
+impl SrpClient {
+    pub fn new(params: SrpParams, email: &str, password: &[u8]) -> SrpClient {
+        let private = BigUint::random_range(&BigUint::one(), &params.n);
+        SrpClient::with_private(params, email, password, private)
+    }
+
+    /// A client using a chosen private key a rather than a random one
+    ///
+    pub fn with_private(
+        params: SrpParams,
+        email: &str,
+        password: &[u8],
+        private: BigUint,
+    ) -> SrpClient {
+        let public = params.g.modpow(&private, &params.n);
+        SrpClient {
+            params,
+            email: email.to_string(),
+            password: password.to_vec(),
+            private,
+            public,
+        }
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    /// A = g^a
+    ///
+    pub fn public(&self) -> &BigUint {
+        &self.public
+    }
+
+    /// The proof of the session key for the server's salt and public key
+    ///
+    pub fn proof(&self, salt: &[u8], b_public: &BigUint) -> Vec<u8> {
+        key_proof(&self.session_key(salt, b_public), salt)
+    }
+
+    /// The session key for the server's salt and public key
+    ///
+    pub fn session_key(&self, salt: &[u8], b_public: &BigUint) -> Vec<u8> {
+        let n = &self.params.n;
+        let x = private_key(salt, &self.password);
+        let u = scrambler(&self.params, &self.public, b_public);
+
+        //S = (B - kg^x)^(a + ux), with B - kg^x taken mod N
+        let kgx = &(&self.params.k * &self.params.g.modpow(&x, n)) % n;
+        let base = &(&(b_public % n) + n) - &kgx;
+        let exponent = &self.private + &(&u * &x);
+        session_key(&base.modpow(&exponent, n))
+    }
+}
+
+/// Run a login from start to finish, returning whether the server accepted it
+///
+pub fn login(client: &SrpClient, server: &mut SrpServer) -> std::result::Result<bool, SrpError> {
+    let (salt, b_public) = server.hello(client.email(), client.public())?;
+    server.verify(&client.proof(&salt, &b_public))
+}
+
+/// Log in without the password by sending A = multiple * N, so the server's secret is
+/// (A * v^u)^b = 0 mod N and the session key is the hash of zero. Returns the session key
+/// if the server accepted it.
+///
+pub fn zero_key_login(
+    server: &mut SrpServer,
+    email: &str,
+    multiple: u64,
+) -> std::result::Result<Option<Vec<u8>>, SrpError> {
+    let a_public = &server.params.n * &BigUint::from_u64(multiple);
+    let (salt, _) = server.hello(email, &a_public)?;
+    let key = session_key(&BigUint::zero());
+    Ok(server.verify(&key_proof(&key, &salt))?.then_some(key))
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+const EMAIL: &str = "user@example.com";
+
+#[test]
+fn test_srp_login() {
+    let params = SrpParams::nist();
+    let mut server = SrpServer::new(params.clone(), EMAIL, b"hunter2");
+
+    let client = SrpClient::new(params.clone(), EMAIL, b"hunter2");
+    assert_eq!(login(&client, &mut server), Ok(true));
+
+    let client = SrpClient::new(params.clone(), EMAIL, b"hunter3");
+    assert_eq!(login(&client, &mut server), Ok(false));
+
+    let client = SrpClient::new(params, "someone@example.com", b"hunter2");
+    assert_eq!(login(&client, &mut server), Err(SrpError::UnknownUser));
+    assert_eq!(server.verify(&[]), Err(SrpError::NoSession));
+}
+
+#[test]
+fn test_srp_zero_key_login() {
+    let mut server = SrpServer::new(SrpParams::nist(), EMAIL, b"hunter2");
+    for multiple in 0..3 {
+        assert_eq!(
+            zero_key_login(&mut server, EMAIL, multiple),
+            Ok(Some(sha256(&[0]).to_vec()))
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use crate::error::{CryptoError, Result};
+use crate::hex_converter::{hex_decode, hex_encode};
+use crate::hmac::hmac;
+use crate::sha1::{Sha1, DIGEST_LEN};
+use std::cmp::Reverse;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+//How many guesses can be retried (on average per byte) before giving up
+const MAX_RETRIES_PER_BYTE: usize = 4;
+
+//How long the server waits for a request, so a client that never sends one can't stall it
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Compare byte by byte, sleeping after each matching byte and returning at the first
+/// mismatch. How long it takes leaks how many leading bytes are right.
+///
+pub fn insecure_compare(lhs: &[u8], rhs: &[u8], delay: Duration) -> bool {
+    for (a, b) in lhs.iter().zip(rhs.iter()) {
+        if a != b {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+    lhs.len() == rhs.len()
+}
+
+/// A tiny HTTP server on 127.0.0.1 that accepts GET /test?file=<name>&signature=<hex> when the
+/// signature is the HMAC-SHA1 of the file name, checked with insecure_compare.
+/// Requests are handled one at a time, and dropped if they don't arrive within a second.
+/// The server stops when dropped.
+///
+pub struct HmacServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HmacServer {
+    /// Start the server on a free port with the given key and per-byte comparison delay
+    ///
+    pub fn start(key: &[u8], delay: Duration) -> io::Result<HmacServer> {
+        HmacServer::start_truncated(key, delay, DIGEST_LEN)
+    }
+
+    /// Start a server that only wants the first mac_len bytes of the HMAC, so an attack on a
+    /// realistic delay can be tried on a short MAC without taking hours
+    ///
+    pub fn start_truncated(key: &[u8], delay: Duration, mac_len: usize) -> io::Result<HmacServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let key = key.to_vec();
+        let mac_len = mac_len.min(DIGEST_LEN);
+        let stop = Arc::clone(&shutdown);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                //A client that goes away mid request is no reason to stop serving
+                if let Ok(stream) = stream {
+                    let _ = handle_connection(stream, &key, mac_len, delay);
+                }
+            }
+        });
+
+        Ok(HmacServer {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for HmacServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        //Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    key: &[u8],
+    mac_len: usize,
+    delay: Duration,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    if !request_line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "request ended before the request line",
+        ));
+    }
+    //Skip the headers
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let status = match parse_request(&request_line) {
+        Some((file, signature)) => {
+            let mac = hmac::<Sha1>(key, file.as_bytes());
+            if insecure_compare(&mac[..mac_len], &signature, delay) {
+                "200 OK"
+            } else {
+                "500 Internal Server Error"
+            }
+        }
+        None => "400 Bad Request",
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )
+}
+
+//Pull the file and decoded signature out of "GET /test?file=...&signature=... HTTP/1.1"
+fn parse_request(request_line: &str) -> Option<(String, Vec<u8>)> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let (path, query) = parts.next()?.split_once('?')?;
+    if path != "/test" {
+        return None;
+    }
+
+    let mut file = None;
+    let mut signature = None;
+    for pair in query.split('&') {
+        match pair.split_once('=')? {
+            ("file", value) => file = Some(value.to_string()),
+            ("signature", value) => signature = hex_decode(value).ok(),
+            _ => (),
+        }
+    }
+    Some((file?, signature?))
+}
+
+/// Ask the server to check a signature for the file. Returns whether it was accepted and
+/// how long the server took to answer.
+///
+pub fn timed_request(
+    addr: SocketAddr,
+    file: &str,
+    signature: &[u8],
+) -> io::Result<(bool, Duration)> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_nodelay(true)?;
+
+    let start = Instant::now();
+    write!(
+        stream,
+        "GET /test?file={}&signature={} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        file,
+        hex_encode(signature),
+        addr
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let elapsed = start.elapsed();
+
+    Ok((response.starts_with("HTTP/1.1 200"), elapsed))
+}
+
+/// How much timing data to gather for each byte of the MAC. Every candidate is timed samples
+/// times, then the finalists that were slowest are timed again confirm_samples times.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimingAttack {
+    pub samples: usize,
+    pub finalists: usize,
+    pub confirm_samples: usize,
+}
+
+/// Recover a valid MAC of mac_len bytes from an oracle that says whether a MAC was accepted
+/// and how long it took. Each byte is the candidate that makes the comparison run longest.
+/// Scheduling noise only ever adds time, so each candidate is judged by its fastest sample.
+/// The last byte is found by acceptance rather than timing.
+///
+/// Each right byte adds the leak to the typical time of every candidate after it, which
+/// gives an estimate of the leak to check guesses against. A winner that doesn't stand out
+/// by half the leak is guessed again, and if the typical time doesn't go up by half the
+/// leak the previous byte was wrong and is guessed again.
+///
+pub fn recover_mac<F: FnMut(&[u8]) -> Result<(bool, Duration)>>(
+    mut oracle: F,
+    mac_len: usize,
+    attack: &TimingAttack,
+) -> Result<Vec<u8>> {
+    if mac_len == 0 || attack.samples == 0 || attack.finalists == 0 {
+        return Err(CryptoError::NoSolution(
+            "need a MAC length, samples and finalists",
+        ));
+    }
+
+    let mut mac = vec![0u8; mac_len];
+    let all: Vec<u8> = (0..=255).collect();
+    //Typical time at each position decided so far, and how far its winner stood out
+    let mut decided: Vec<(Duration, Duration)> = Vec::with_capacity(mac_len);
+    let mut retries = 0;
+    loop {
+        let i = decided.len();
+        if i == mac_len - 1 {
+            if accepted_last_byte(&mut oracle, &mut mac)? {
+                return Ok(mac);
+            }
+            count_retry(&mut retries, mac_len)?;
+            decided.pop();
+            continue;
+        }
+
+        let ranked = rank_candidates(&mut oracle, &mut mac, i, &all, attack.samples)?;
+        let typical = ranked[ranked.len() / 2].0;
+        let base = decided.first().map_or(typical, |&(t, _)| t);
+
+        //Every candidate should be slower than at the last position by about the leak,
+        //estimated from the first winner until there is a rise to go on
+        if let Some(&(prev_typical, prev_lead)) = decided.last() {
+            let leak = match i {
+                1 => prev_lead,
+                _ => prev_typical.saturating_sub(base) / (i as u32 - 1),
+            };
+            if typical.saturating_sub(prev_typical) < leak / 2 {
+                count_retry(&mut retries, mac_len)?;
+                decided.pop();
+                continue;
+            }
+        }
+
+        let finalists: Vec<u8> = ranked
+            .iter()
+            .take(attack.finalists)
+            .map(|&(_, candidate)| candidate)
+            .collect();
+        let confirm_samples = attack.confirm_samples.max(1);
+        let confirmed = rank_candidates(&mut oracle, &mut mac, i, &finalists, confirm_samples)?;
+        let (time, winner) = confirmed[0];
+        //How far the winner stands out from the runner up, timed the same number of times
+        let runner_up = confirmed.get(1).map_or(typical, |&(t, _)| t);
+        let lead = time.saturating_sub(runner_up);
+
+        //After i right bytes the typical time has gone up by i leaks
+        if i > 0 && lead < typical.saturating_sub(base) / (2 * i as u32) {
+            count_retry(&mut retries, mac_len)?;
+            continue;
+        }
+        mac[i] = winner;
+        decided.push((typical, lead));
+    }
+}
+
+//Give up once the guesses have been retried too many times for the length of the MAC
+fn count_retry(retries: &mut usize, mac_len: usize) -> Result<()> {
+    *retries += 1;
+    if *retries > MAX_RETRIES_PER_BYTE * mac_len {
+        return Err(CryptoError::NoSolution("timings never settled on a MAC"));
+    }
+    Ok(())
+}
+
+//Try every value of the last byte until one is accepted
+fn accepted_last_byte<F: FnMut(&[u8]) -> Result<(bool, Duration)>>(
+    oracle: &mut F,
+    mac: &mut [u8],
+) -> Result<bool> {
+    let last = mac.len() - 1;
+    for b in 0..=255 {
+        mac[last] = b;
+        if oracle(mac)?.0 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+//Candidates for the byte at index with their fastest times, slowest first. Candidates are
+//interleaved within each round so drift in the timings affects them all alike.
+fn rank_candidates<F: FnMut(&[u8]) -> Result<(bool, Duration)>>(
+    oracle: &mut F,
+    mac: &mut [u8],
+    index: usize,
+    candidates: &[u8],
+    samples: usize,
+) -> Result<Vec<(Duration, u8)>> {
+    let mut timings = vec![Vec::with_capacity(samples); candidates.len()];
+    for _ in 0..samples {
+        for (times, &candidate) in timings.iter_mut().zip(candidates.iter()) {
+            mac[index] = candidate;
+            times.push(oracle(mac)?.1);
+        }
+    }
+
+    let mut ranked: Vec<(Duration, u8)> = timings
+        .iter()
+        .zip(candidates.iter())
+        .map(|(times, &candidate)| (*times.iter().min().unwrap(), candidate))
+        .collect();
+    ranked.sort_by_key(|&(time, _)| Reverse(time));
+    Ok(ranked)
+}
+
+/// Unit tests
+///
+#[cfg(test)]
+use crate::random::random_range;
+
+#[test]
+fn test_insecure_compare() {
+    let delay = Duration::from_millis(0);
+    assert!(insecure_compare(b"abc", b"abc", delay));
+    assert!(!insecure_compare(b"abc", b"abd", delay));
+    assert!(!insecure_compare(b"abc", b"ab", delay));
+}
+
+#[test]
+fn test_parse_request() {
+    assert_eq!(
+        parse_request("GET /test?file=foo&signature=0aFF HTTP/1.1\r\n"),
+        Some(("foo".to_string(), vec![0x0a, 0xff]))
+    );
+    assert_eq!(parse_request("GET /test?file=foo HTTP/1.1\r\n"), None);
+    assert_eq!(
+        parse_request("GET /other?file=foo&signature=00 HTTP/1.1\r\n"),
+        None
+    );
+    assert_eq!(
+        parse_request("POST /test?file=foo&signature=00 HTTP/1.1\r\n"),
+        None
+    );
+}
+
+#[test]
+fn test_hmac_server() {
+    let server = HmacServer::start(b"key", Duration::from_millis(0)).unwrap();
+    let valid = hmac::<Sha1>(b"key", b"foo");
+    assert!(timed_request(server.addr(), "foo", &valid).unwrap().0);
+    assert!(!timed_request(server.addr(), "bar", &valid).unwrap().0);
+    assert!(!timed_request(server.addr(), "foo", &valid[..19]).unwrap().0);
+}
+
+#[test]
+fn test_hmac_server_stalled_client() {
+    let server = HmacServer::start(b"key", Duration::from_millis(0)).unwrap();
+    let valid = hmac::<Sha1>(b"key", b"foo");
+
+    //A client that never sends a request, and one that stops part way through the request
+    //line, are dropped and the server carries on
+    let _silent = TcpStream::connect(server.addr()).unwrap();
+    let mut short = TcpStream::connect(server.addr()).unwrap();
+    write!(short, "GET /test?file=foo").unwrap();
+    short.shutdown(std::net::Shutdown::Write).unwrap();
+    let mut response = String::new();
+    short.read_to_string(&mut response).unwrap();
+    assert!(response.is_empty());
+
+    assert!(timed_request(server.addr(), "foo", &valid).unwrap().0);
+}
+
+#[test]
+fn test_recover_mac_simulated() {
+    //Simulated timings with noise up to three times the per-byte leak
+    let secret = [0x13, 0x37, 0xca, 0xfe, 0x42];
+    let oracle = |mac: &[u8]| {
+        let matching = mac
+            .iter()
+            .zip(secret.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let noise = random_range(0, 300) as u64;
+        Ok((
+            mac == secret,
+            Duration::from_micros(1000 + 100 * matching as u64 + noise),
+        ))
+    };
+    let attack = TimingAttack {
+        samples: 15,
+        finalists: 5,
+        confirm_samples: 45,
+    };
+    assert_eq!(recover_mac(oracle, 5, &attack).unwrap(), secret);
+}
+
+#[test]
+fn test_recover_mac_server_5ms() {
+    //The challenge 32 leak for real, against a 3 byte MAC so it takes seconds not hours
+    let server = HmacServer::start_truncated(b"key", Duration::from_millis(5), 3).unwrap();
+    let attack = TimingAttack {
+        samples: 3,
+        finalists: 5,
+        confirm_samples: 5,
+    };
+    let mac = recover_mac(
+        |signature| Ok(timed_request(server.addr(), "foo", signature)?),
+        3,
+        &attack,
+    )
+    .unwrap();
+    assert_eq!(mac, hmac::<Sha1>(b"key", b"foo")[..3].to_vec());
+}
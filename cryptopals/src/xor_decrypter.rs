@@ -3,16 +3,16 @@ static PRINTABLE_ASCII_SCORES: [u32; 256] = init_scores_table();
 /// Attempts to decrypt the given bytes assumign a single character xor key
 /// and that the output is plaintext.
 ///
-pub fn single_char_xor_plaintext_decrypt(encrypted: &[u8]) -> Option<(Vec<u8>, u32, u8)> {
+pub fn single_char_xor_plaintext_decrypt(encrypted: &[u8]) -> Option<(Vec<u8>, u64, u8)> {
     //Count ascii character occurrences
-    let mut counts: [u8; 256] = [0; 256];
+    let mut counts: [usize; 256] = [0; 256];
     for b in encrypted.iter() {
         counts[*b as usize] += 1;
     }
 
     //Find the most frequent letter (likely to be one of 'etaoin shrdlu')
     let mut most_common_ascii_encrypted = 0u8;
-    let mut max_val = 0u8;
+    let mut max_val = 0;
     for (i, &c) in counts.iter().enumerate() {
         if c >= max_val {
             max_val = c;
@@ -52,6 +52,27 @@ pub fn single_char_xor_plaintext_decrypt(encrypted: &[u8]) -> Option<(Vec<u8>, u
     }
 }
 
+/// Transposes rows of bytes into columns such that abc def gh => adg beh cf.
+/// Rows can be different lengths, shorter rows just don't contribute to the later columns.
+///
+pub fn transpose(rows: &[&[u8]]) -> Vec<Vec<u8>> {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    (0..width)
+        .map(|i| rows.iter().filter_map(|r| r.get(i).copied()).collect())
+        .collect()
+}
+
+/// Breaks data that has had each column xor'd with a single character, e.g. repeating key
+/// xor split into rows of the key length or ciphertexts that share a keystream.
+/// Returns the likely key byte for each column, None where no key gives plaintext.
+///
+pub fn break_columns(rows: &[&[u8]]) -> Vec<Option<u8>> {
+    transpose(rows)
+        .iter()
+        .map(|column| single_char_xor_plaintext_decrypt(column).map(|(_, _, key)| key))
+        .collect()
+}
+
 /// Perform the xor decryption and return the value if it is printable ascii
 ///
 fn try_decrypt_char(c: u8, key: u8) -> Option<u8> {
@@ -64,10 +85,10 @@ fn try_decrypt_char(c: u8, key: u8) -> Option<u8> {
 
 /// Score the potential plaintext based on letter frequencies, strings with a high score are more likely to be English text
 ///
-fn freq_score(bytes: &[u8]) -> u32 {
+fn freq_score(bytes: &[u8]) -> u64 {
     bytes
         .iter()
-        .map(|b| PRINTABLE_ASCII_SCORES[*b as usize])
+        .map(|b| u64::from(PRINTABLE_ASCII_SCORES[*b as usize]))
         .sum()
 }
 
@@ -131,3 +152,46 @@ const fn init_scores_table() -> [u32; 256] {
     lut[b'Z' as usize] = 7836;
     lut
 }
+
+/// Unit tests
+///
+#[test]
+fn test_transpose() {
+    let m: [&[u8]; 3] = [&[0, 1, 2], &[3, 4, 5], &[6, 7, 8]];
+    let t = transpose(&m);
+    assert_eq!(t, [vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]]);
+}
+
+#[test]
+fn test_transpose_ragged() {
+    let m: [&[u8]; 3] = [&[0, 1, 2], &[3, 4, 5], &[6, 7]];
+    let t = transpose(&m);
+    assert_eq!(t, [vec![0, 3, 6], vec![1, 4, 7], vec![2, 5]]);
+}
+
+#[test]
+fn test_break_columns() {
+    let plaintext = b"Now that the party is jumping, with the bass kicked in";
+    let key = b"ICE";
+    let encrypted: Vec<u8> = plaintext
+        .iter()
+        .enumerate()
+        .map(|(i, b)| b ^ key[i % key.len()])
+        .collect();
+    let rows: Vec<&[u8]> = encrypted.chunks(key.len()).collect();
+    let found: Vec<u8> = break_columns(&rows)
+        .into_iter()
+        .map(Option::unwrap)
+        .collect();
+    assert_eq!(found, key);
+}
+
+#[test]
+fn test_single_char_xor_long_column() {
+    //Thousands of spaces mustn't overflow the counts or the score
+    let plaintext = b"the quick brown fox jumps over the lazy dog ".repeat(400);
+    let encrypted: Vec<u8> = plaintext.iter().map(|b| b ^ 0x42).collect();
+    let (decrypted, _, key) = single_char_xor_plaintext_decrypt(&encrypted).unwrap();
+    assert_eq!(key, 0x42);
+    assert_eq!(decrypted, plaintext);
+}
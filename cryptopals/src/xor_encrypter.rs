@@ -8,3 +8,24 @@ pub fn xor_encrypt(text: &str, key: &str) -> Vec<u8> {
     }
     encrypted
 }
+
+/// XOR two byte strings together. The output is the length of the shorter input.
+///
+pub fn xor_bytes(lhs: &[u8], rhs: &[u8]) -> Vec<u8> {
+    lhs.iter().zip(rhs.iter()).map(|(a, b)| a ^ b).collect()
+}
+
+/// Unit tests
+///
+#[test]
+fn test_xor_bytes() {
+    assert_eq!(
+        xor_bytes(&[0xff, 0x0f, 0x00], &[0x0f, 0x0f, 0xaa]),
+        [0xf0, 0x00, 0xaa]
+    );
+}
+
+#[test]
+fn test_xor_bytes_mismatched_length() {
+    assert_eq!(xor_bytes(b"abc", b"a"), [0]);
+}
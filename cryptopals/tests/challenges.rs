@@ -0,0 +1,219 @@
+use cryptopals::challenges::{self, CHALLENGES};
+
+/// Solve a single challenge and fail with its answer if it does not match the known answer
+///
+fn verify(number: u32) {
+    let challenge = challenges::find(number).expect("challenge is not registered");
+    if let Err(failure) = challenge.verify() {
+        panic!(
+            "Challenge {} ({}) failed: {}",
+            challenge.number, challenge.title, failure
+        );
+    }
+}
+
+#[test]
+fn test_every_challenge_has_a_test() {
+    let tested = [
+        1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 19, 21, 22, 23, 24, 25, 26, 27,
+        28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40,
+    ];
+    let registered: Vec<u32> = CHALLENGES.iter().map(|c| c.number).collect();
+    assert_eq!(registered, tested);
+}
+
+#[test]
+fn test_challenge1() {
+    verify(1);
+}
+
+#[test]
+fn test_challenge2() {
+    verify(2);
+}
+
+#[test]
+fn test_challenge3() {
+    verify(3);
+}
+
+#[test]
+fn test_challenge4() {
+    verify(4);
+}
+
+#[test]
+fn test_challenge5() {
+    verify(5);
+}
+
+#[test]
+fn test_challenge6() {
+    verify(6);
+}
+
+#[test]
+fn test_challenge7() {
+    verify(7);
+}
+
+#[test]
+fn test_challenge8() {
+    verify(8);
+}
+
+#[test]
+fn test_challenge9() {
+    verify(9);
+}
+
+#[test]
+fn test_challenge10() {
+    verify(10);
+}
+
+#[test]
+fn test_challenge11() {
+    verify(11);
+}
+
+#[test]
+fn test_challenge12() {
+    verify(12);
+}
+
+#[test]
+fn test_challenge13() {
+    verify(13);
+}
+
+#[test]
+fn test_challenge14() {
+    verify(14);
+}
+
+#[test]
+fn test_challenge15() {
+    verify(15);
+}
+
+#[test]
+fn test_challenge16() {
+    verify(16);
+}
+
+#[test]
+fn test_challenge17() {
+    verify(17);
+}
+
+#[test]
+fn test_challenge19() {
+    verify(19);
+}
+
+#[test]
+fn test_challenge21() {
+    verify(21);
+}
+
+#[test]
+fn test_challenge22() {
+    verify(22);
+}
+
+#[test]
+fn test_challenge23() {
+    verify(23);
+}
+
+#[test]
+fn test_challenge24() {
+    verify(24);
+}
+
+#[test]
+fn test_challenge25() {
+    verify(25);
+}
+
+#[test]
+fn test_challenge26() {
+    verify(26);
+}
+
+#[test]
+fn test_challenge27() {
+    verify(27);
+}
+
+#[test]
+fn test_challenge28() {
+    verify(28);
+}
+
+#[test]
+fn test_challenge29() {
+    verify(29);
+}
+
+#[test]
+fn test_challenge30() {
+    verify(30);
+}
+
+//The timing attacks take minutes against a real server even with the delays scaled down from
+//the challenges, and are thrown off by the noise of other tests running in parallel. Run
+//them with --ignored on a quiet machine. The 5ms leak itself is tested on a short MAC in
+//timing_leak.
+#[test]
+#[ignore]
+fn test_challenge31() {
+    verify(31);
+}
+
+#[test]
+#[ignore]
+fn test_challenge32() {
+    verify(32);
+}
+
+#[test]
+fn test_challenge33() {
+    verify(33);
+}
+
+#[test]
+fn test_challenge34() {
+    verify(34);
+}
+
+#[test]
+fn test_challenge35() {
+    verify(35);
+}
+
+#[test]
+fn test_challenge36() {
+    verify(36);
+}
+
+#[test]
+fn test_challenge37() {
+    verify(37);
+}
+
+#[test]
+fn test_challenge38() {
+    verify(38);
+}
+
+#[test]
+fn test_challenge39() {
+    verify(39);
+}
+
+#[test]
+fn test_challenge40() {
+    verify(40);
+}
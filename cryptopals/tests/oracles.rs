@@ -0,0 +1,29 @@
+use cryptopals::aes::{Aes, BLOCK_SIZE};
+use cryptopals::cbc_key_as_iv::{recover_key_as_iv, AsciiError, KeyAsIvService};
+use cryptopals::modes::cbc_decrypt;
+
+#[test]
+fn test_key_as_iv_recovered_key_decrypts() {
+    let service = KeyAsIvService::new();
+    let message = b"Some message that is at least three blocks long, to be tampered with";
+    let encrypted = service.encrypt(message);
+
+    let key = recover_key_as_iv(|ct| service.check_ascii(ct), &encrypted).unwrap();
+
+    //With the key (which is also the IV) we can read the original message
+    let cipher = Aes::new(&key).unwrap();
+    assert_eq!(
+        cbc_decrypt(&cipher, &key, &encrypted).unwrap(),
+        &message[..]
+    );
+}
+
+#[test]
+fn test_key_as_iv_service_leaks_plaintext() {
+    let service = KeyAsIvService::new();
+    let encrypted = service.encrypt(&[0xff; BLOCK_SIZE]);
+    match service.check_ascii(&encrypted) {
+        Err(AsciiError::HighAscii(plaintext)) => assert_eq!(plaintext, vec![0xff; BLOCK_SIZE]),
+        other => panic!("expected a high-ASCII error, got {:?}", other),
+    }
+}